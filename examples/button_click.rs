@@ -1,4 +1,3 @@
-use cstr_core::CString;
 use embedded_graphics::pixelcolor::Rgb565;
 use embedded_graphics::prelude::*;
 use embedded_graphics_simulator::{
@@ -68,7 +67,7 @@ impl BtnTest {
             .set_grid_cell(GridAlign::Stretch, 0, 1, GridAlign::Stretch, 0, 1);
 
             let mut btn_lbl = Label::new(obj);
-            btn_lbl.set_text(CString::new("0.1mm").unwrap().as_c_str());
+            btn_lbl.set_text("0.1mm");
             btn_lbl.align_to(obj, Align::Center, 0, 0);
         });
 
@@ -79,7 +78,7 @@ impl BtnTest {
             .set_grid_cell(GridAlign::Stretch, 1, 1, GridAlign::Stretch, 0, 1);
 
             let mut btn_lbl = Label::new(obj);
-            btn_lbl.set_text(CString::new("1mm").unwrap().as_c_str());
+            btn_lbl.set_text("1mm");
             btn_lbl.align_to(obj, Align::Center, 0, 0);
         });
 
@@ -90,7 +89,7 @@ impl BtnTest {
             .set_grid_cell(GridAlign::Stretch, 2, 1, GridAlign::Stretch, 0, 1);
 
             let mut btn_lbl = Label::new(obj);
-            btn_lbl.set_text(CString::new("10mm").unwrap().as_c_str());
+            btn_lbl.set_text("10mm");
             btn_lbl.align_to(obj, Align::Center, 0, 0);
         });
 
@@ -101,7 +100,7 @@ impl BtnTest {
             .set_grid_cell(GridAlign::Stretch, 0, 1, GridAlign::Stretch, 1, 1);
 
             let mut btn_lbl = Label::new(obj);
-            btn_lbl.set_text(CString::new("UP").unwrap().as_c_str());
+            btn_lbl.set_text("UP");
             btn_lbl.align_to(obj, Align::Center, 0, 0);
         });
 
@@ -112,7 +111,7 @@ impl BtnTest {
             .set_grid_cell(GridAlign::Stretch, 1, 1, GridAlign::Stretch, 1, 1);
 
             let mut btn_lbl = Label::new(obj);
-            btn_lbl.set_text(CString::new("HOME").unwrap().as_c_str());
+            btn_lbl.set_text("HOME");
             btn_lbl.align_to(obj, Align::Center, 0, 0);
         });
 
@@ -123,7 +122,7 @@ impl BtnTest {
             .set_grid_cell(GridAlign::Stretch, 2, 1, GridAlign::Stretch, 1, 1);
 
             let mut btn_lbl = Label::new(obj);
-            btn_lbl.set_text(CString::new("DOWN").unwrap().as_c_str());
+            btn_lbl.set_text("DOWN");
             btn_lbl.align_to(obj, Align::Center, 0, 0);
         });
 
@@ -134,12 +133,12 @@ impl BtnTest {
             .set_grid_cell(GridAlign::Stretch, 0, 3, GridAlign::Stretch, 2, 1);
 
             let mut btn_lbl = Label::new(obj);
-            btn_lbl.set_text(CString::new("STOP").unwrap().as_c_str());
+            btn_lbl.set_text("STOP");
             btn_lbl.align_to(obj, Align::Center, 0, 0);
         });
 
         let mut current_pos = Label::new(screen).apply(|obj| {
-            obj.set_text(CString::new("0.0").unwrap().as_c_str());
+            obj.set_text("0.0");
             obj.set_grid_cell(GridAlign::Center, 0, 3, GridAlign::Center, 3, 1);
         });
 
@@ -173,26 +172,17 @@ fn main() {
         [MaybeUninit::<Rgb565>::uninit(); LVGL_BUFFER_LEN];
     let mut displayd = Display::new(&lvgl, displays, unsafe { &mut DRAW_BUFFER });
 
-    pub fn new_screen<D, C>(
-        display: &Display<D>,
-        init_f: impl FnOnce(&mut Screen<C>) -> C,
-    ) -> Screen<C> {
-        let mut screen = Screen::<C>::new(display);
-        let context = init_f(&mut screen);
-        screen.context().replace(context);
-        screen
-    }
-
     pub fn idle_task(
         mut lvgl: Lvgl,
         mut display: Display<SimulatorDisplay<Rgb565>>,
         mut window: Window,
     ) {
-        let mut ui = new_screen(&display, |screen| BtnTest::new(screen));
+        let mut screen = Screen::new(&display);
+        let mut ui = BtnTest::new(&mut screen);
 
-        display.load_screen(&mut ui);
+        display.load_screen(&mut screen);
 
-        ui.context().as_mut().unwrap().refresh();
+        ui.refresh();
 
         let mut lvgl_input_device = InputDevice::<TouchPad>::new(&mut display);
 
@@ -218,8 +208,11 @@ fn main() {
             e
         }
 
+        let mut ticks = lvgl.std_ticks();
+
         'running: loop {
-            lvgl.run_tasks();
+            ticks.update();
+            lvgl.run_tasks(&mut ui);
 
             window.update(&display);
 
@@ -249,9 +242,6 @@ fn main() {
             }
 
             thread::sleep(Duration::from_millis(1));
-            lvgl.ticks().inc(1000);
-
-            //ticks.inc(loop_started.elapsed().as_millis() as u32);
         }
     }
 
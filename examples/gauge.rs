@@ -3,64 +3,95 @@ use embedded_graphics::prelude::*;
 use embedded_graphics_simulator::{
     OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent, Window,
 };
-use lvgl::style::{Opacity, Style};
-use lvgl::widgets::Gauge;
-use lvgl::{self, Align, Color, LvError, Part, State, Widget, UI};
-use lvgl_sys;
-use std::time::Instant;
-
-fn main() -> Result<(), LvError> {
-    let display: SimulatorDisplay<Rgb565> = SimulatorDisplay::new(Size::new(
-        lvgl_sys::LV_HOR_RES_MAX,
-        lvgl_sys::LV_VER_RES_MAX,
-    ));
+
+use lvgl::core::{Anim, AnimPath, Display, Lvgl, ObjExt, Screen, ANIM_REPEAT_INFINITE};
+use lvgl::style::Align;
+use lvgl::widgets::{Meter, MeterIndicator};
+use std::{thread, time::Duration};
+
+use core::mem::MaybeUninit;
+
+const LVGL_BUFFER_LEN: usize = 76800;
+
+pub struct Gauge {
+    meter: Meter<Gauge>,
+    needle: MeterIndicator,
+}
+
+impl Gauge {
+    pub fn new(screen: &mut Screen<Self>) -> Self {
+        let mut meter = Meter::new(screen);
+        meter.set_size(200, 200);
+        meter.align_to(screen, Align::Center, 0, 0);
+
+        let mut scale = meter.add_scale();
+        meter
+            .set_scale_ticks(&mut scale, 41, 2, 10, Rgb565::CSS_GRAY)
+            .set_scale_major_ticks(&mut scale, 8, 4, 15, Rgb565::BLACK, 10)
+            .set_scale_range(&mut scale, 0, 100, 270, 135);
+
+        // Blue arc and scale lines at the start
+        let mut indicator = meter.add_arc(&mut scale, 3, Rgb565::BLUE, 0);
+        meter
+            .set_indicator_start_value(&mut indicator, 0)
+            .set_indicator_end_value(&mut indicator, 20);
+        let mut indicator =
+            meter.add_scale_lines(&mut scale, Rgb565::BLUE, Rgb565::BLUE, false, 0);
+        meter
+            .set_indicator_start_value(&mut indicator, 0)
+            .set_indicator_end_value(&mut indicator, 20);
+
+        // Red arc and scale lines at the end
+        let mut indicator = meter.add_arc(&mut scale, 3, Rgb565::RED, 0);
+        meter
+            .set_indicator_start_value(&mut indicator, 80)
+            .set_indicator_end_value(&mut indicator, 100);
+        let mut indicator = meter.add_scale_lines(&mut scale, Rgb565::RED, Rgb565::RED, false, 0);
+        meter
+            .set_indicator_start_value(&mut indicator, 80)
+            .set_indicator_end_value(&mut indicator, 100);
+
+        let needle = meter.add_needle_line(&mut scale, 4, Rgb565::CSS_GRAY, -10);
+
+        Self { meter, needle }
+    }
+}
+
+fn main() {
+    let display: SimulatorDisplay<Rgb565> = SimulatorDisplay::new(Size::new(320, 240));
 
     let output_settings = OutputSettingsBuilder::new().scale(2).build();
     let mut window = Window::new("Gauge Example", &output_settings);
 
-    let mut ui = UI::init()?;
-
-    // Implement and register your display:
-    ui.disp_drv_register(display)?;
-
-    // Create screen and widgets
-    let mut screen = ui.scr_act()?;
-
-    let mut screen_style = Style::default();
-    screen_style.set_bg_color(State::DEFAULT, Color::from_rgb((0, 0, 0)));
-    screen.add_style(Part::Main, screen_style)?;
-
-    // Create the gauge
-    let mut gauge_style = Style::default();
-    // Set a background color and a radius
-    gauge_style.set_radius(State::DEFAULT, 5);
-    gauge_style.set_bg_opa(State::DEFAULT, Opacity::OPA_COVER);
-    gauge_style.set_bg_color(State::DEFAULT, Color::from_rgb((192, 192, 192)));
-    // Set some paddings
-    gauge_style.set_pad_inner(State::DEFAULT, 20);
-    gauge_style.set_pad_top(State::DEFAULT, 20);
-    gauge_style.set_pad_left(State::DEFAULT, 5);
-    gauge_style.set_pad_right(State::DEFAULT, 5);
-
-    gauge_style.set_scale_end_color(State::DEFAULT, Color::from_rgb((255, 0, 0)));
-    gauge_style.set_line_color(State::DEFAULT, Color::from_rgb((255, 255, 255)));
-    gauge_style.set_scale_grad_color(State::DEFAULT, Color::from_rgb((0, 0, 255)));
-    gauge_style.set_line_width(State::DEFAULT, 2);
-    gauge_style.set_scale_end_line_width(State::DEFAULT, 4);
-    gauge_style.set_scale_end_border_width(State::DEFAULT, 4);
-
-    let mut gauge = Gauge::new(&mut screen)?;
-    gauge.add_style(Part::Main, gauge_style)?;
-    gauge.set_align(&mut screen, Align::Center, 0, 0)?;
-    gauge.set_value(0, 50)?;
-
-    let mut i = 0;
-    let mut loop_started = Instant::now();
+    let mut lvgl = Lvgl::new();
+
+    static mut DRAW_BUFFER: [MaybeUninit<Rgb565>; LVGL_BUFFER_LEN] =
+        [MaybeUninit::<Rgb565>::uninit(); LVGL_BUFFER_LEN];
+    let mut display = Display::new(&lvgl, display, unsafe { &mut DRAW_BUFFER });
+
+    let mut screen = Screen::new(&display);
+    let mut ui = Gauge::new(&mut screen);
+    display.load_screen(&mut screen);
+
+    // Sweep the needle back and forth
+    let mut anim = Anim::new();
+    anim.set_values(0, 100)
+        .set_time(2000)
+        .set_playback_time(2000)
+        .set_path(AnimPath::EaseInOut)
+        .set_repeat_count(ANIM_REPEAT_INFINITE)
+        .on_exec(|ui: &mut Gauge, value| {
+            ui.meter.set_indicator_value(&mut ui.needle, value);
+        });
+    anim.start();
+
+    let mut ticks = lvgl.std_ticks();
+
     'running: loop {
-        gauge.set_value(0, i)?;
+        ticks.update();
+        lvgl.run_tasks(&mut ui);
 
-        ui.task_handler();
-        window.update(ui.get_display_ref().unwrap());
+        window.update(&display);
 
         for event in window.events() {
             match event {
@@ -75,15 +106,6 @@ fn main() -> Result<(), LvError> {
             }
         }
 
-        if i > 99 {
-            i = 0;
-        } else {
-            i = i + 1;
-        }
-
-        ui.tick_inc(loop_started.elapsed());
-        loop_started = Instant::now();
+        thread::sleep(Duration::from_millis(1));
     }
-
-    Ok(())
 }
@@ -22,39 +22,32 @@ lazy_static! {
         ("bool", "bool"),
         ("lv_opa_t", "lv_opa_t"),
         ("lv_anim_enable_t", "lv_anim_enable_t"),
-        ("lv_arc_mode_t", "lv_arc_mode_t"),
-        ("lv_bar_mode_t", "lv_bar_mode_t"),
-        ("lv_btnmatrix_ctrl_t", "lv_btnmatrix_ctrl_t"),
-        ("lv_chart_axis_t", "lv_chart_axis_t"),
-        ("lv_chart_type_t", "lv_chart_type_t"),
-        ("lv_chart_update_mode_t", "lv_chart_update_mode_t"),
         ("lv_color_t", "lv_color_t"),
         ("lv_coord_t", "lv_coord_t"),
         ("lv_dir_t", "lv_dir_t"),
         ("lv_img_size_mode_t", "lv_img_size_mode_t"),
         ("lv_imgbtn_state_t", "lv_imgbtn_state_t"),
-        ("lv_keyboard_mode_t", "lv_keyboard_mode_t"),
-        ("lv_label_long_mode_t", "lv_label_long_mode_t"),
-        ("lv_menu_mode_header_t", "lv_menu_mode_header_t"),
-        (
-            "lv_menu_mode_root_back_btn_t",
-            "lv_menu_mode_root_back_btn_t"
-        ),
-        ("lv_roller_mode_t", "lv_roller_mode_t"),
         ("lv_slider_mode_t", "lv_slider_mode_t"),
         ("lv_span_mode_t", "lv_span_mode_t"),
         ("lv_span_overflow_t", "lv_span_overflow_t"),
-        ("lv_table_cell_ctrl_t", "lv_table_cell_ctrl_t"),
         ("lv_text_align_t", "lv_text_align_t"),
         ("lv_coord_t", "lv_coord_t"),
         ("* const cty :: c_char", "_"),
-        ("* mut * const cty :: c_char", "* mut * const cty :: c_char"),
     ]
     .iter()
     .cloned()
     .collect();
 }
 
+// Widgets written by hand in the lvgl crate.
+const HAND_WRITTEN_WIDGETS: &[&str] = &["msgbox"];
+
+// Functions with a hand-written wrapper of the same name in the lvgl crate.
+const HAND_WRITTEN_FUNCS: &[&str] = &["lv_dropdown_set_options", "lv_chart_set_point_count"];
+
+// Functions taking strings that must outlive the object, on top of the `_static` ones.
+const STATIC_STR_FUNCS: &[&str] = &["lv_dropdown_set_text", "lv_textarea_set_accepted_chars"];
+
 #[derive(Debug, Copy, Clone)]
 pub enum WrapperError {
     Skip,
@@ -79,7 +72,7 @@ impl Rusty for LvWidget {
 
     fn code(&self, _parent: &Self::Parent) -> WrapperResult<TokenStream> {
         // We don't generate for the generic Obj
-        if self.name.as_str().eq("obj") {
+        if self.name.as_str().eq("obj") || HAND_WRITTEN_WIDGETS.contains(&self.name.as_str()) {
             return Err(WrapperError::Skip);
         }
 
@@ -108,6 +101,28 @@ impl LvFunc {
         Self { name, args, ret }
     }
 
+    /// Whether lvgl keeps the string arguments instead of copying them.
+    pub fn keeps_str_args(&self) -> bool {
+        self.name.ends_with("_static") || STATIC_STR_FUNCS.contains(&self.name.as_str())
+    }
+
+    // Copied strings are only null terminated for the duration of the call.
+    fn with_str_args(&self, call: TokenStream) -> TokenStream {
+        if self.keeps_str_args() {
+            return call;
+        }
+        self.args
+            .iter()
+            .skip(1)
+            .filter(|arg| arg.typ.is_str())
+            .fold(call, |call, arg| {
+                let ident = arg.get_name_ident();
+                quote! {
+                    crate::support::with_cstr(#ident, |#ident| #call)
+                }
+            })
+    }
+
     pub fn is_method(&self) -> bool {
         if !self.args.is_empty() {
             let first_arg = &self.args[0];
@@ -125,19 +140,32 @@ impl Rusty for LvFunc {
         let new_name = self.name.replace(templ.as_str(), "");
         let original_func_name = format_ident!("{}", self.name.as_str());
 
-        // generate constructor
+        // generate constructor, the first argument is the parent
         if new_name.as_str().eq("create") {
+            let mut args_decl = Vec::new();
+            let mut args_call = Vec::new();
+            for arg in self.args.iter().skip(1) {
+                args_decl.push(arg.code(self)?);
+                args_call.push(arg.get_value_usage(self));
+            }
+            let call = self.with_str_args(quote! {
+                unsafe {
+                    let obj = lvgl_sys::#original_func_name(&mut *parent.raw #(, #args_call)*);
+                    let obj = Obj::from_raw(obj.as_mut().expect("OOM"));
+                    Self { obj }
+                }
+            });
             return Ok(quote! {
-                pub fn new<'a>(parent: &mut impl crate::core::ObjExt<C>) -> Self {
-                    unsafe {
-                        let obj = lvgl_sys::#original_func_name(&mut *parent.raw);
-                        let obj = Obj::from_raw(obj.as_mut().expect("OOM"), parent.context);
-                        Self { obj }
-                    }
+                pub fn new<'a>(parent: &mut impl crate::core::ObjExt<C> #(, #args_decl)*) -> Self {
+                    #call
                 }
             });
         }
 
+        if HAND_WRITTEN_FUNCS.contains(&self.name.as_str()) {
+            return Err(WrapperError::Skip);
+        }
+
         let func_name = format_ident!("{}", new_name);
 
         // We don't deal with methods that return types yet
@@ -211,7 +239,7 @@ impl Rusty for LvFunc {
                 let next_arg = if i == 0 {
                     quote!(&mut *self.raw)
                 } else {
-                    let var = arg.get_value_usage();
+                    let var = arg.get_value_usage(self);
                     quote!(#var)
                 };
                 if args.is_empty() {
@@ -231,13 +259,15 @@ impl Rusty for LvFunc {
             quote!(&mut Self)
         };
 
+        let call = self.with_str_args(quote! {
+            unsafe { lvgl_sys::#original_func_name(#args_call) }
+        });
+
         // TODO: Handle methods that return types
         Ok(quote! {
             pub fn #func_name(#args_decl) -> #ret_type {
                 #args_processing
-                unsafe {
-                    lvgl_sys::#original_func_name(#args_call);
-                }
+                #call;
                 self
             }
         })
@@ -328,12 +358,20 @@ impl LvArg {
         quote! {}
     }
 
-    pub fn get_value_usage(&self) -> TokenStream {
+    pub fn get_value_usage(&self, func: &LvFunc) -> TokenStream {
         let ident = self.get_name_ident();
-        if self.typ.is_str() {
+        if self.typ.is_str() && func.keeps_str_args() {
             quote! {
                 #ident.as_ptr()
             }
+        } else if self.typ.is_anim_enable() {
+            quote! {
+                #ident.into()
+            }
+        } else if self.typ.is_dir() {
+            quote! {
+                #ident.bits()
+            }
         } else {
             quote! {
                 #ident
@@ -349,9 +387,13 @@ impl LvArg {
 impl Rusty for LvArg {
     type Parent = LvFunc;
 
-    fn code(&self, _parent: &Self::Parent) -> WrapperResult<TokenStream> {
+    fn code(&self, parent: &Self::Parent) -> WrapperResult<TokenStream> {
         let name = self.get_name_ident();
-        let typ = self.typ.code(self)?;
+        let typ = if self.typ.is_str() && !parent.keeps_str_args() {
+            quote!(&str)
+        } else {
+            self.typ.code(self)?
+        };
         Ok(quote! {
             #name: #typ
         })
@@ -382,8 +424,12 @@ impl LvType {
         self.literal_name == "* const cty :: c_char"
     }
 
-    pub fn is_str_arry(&self) -> bool {
-        self.literal_name == "* mut * const cty :: c_char"
+    pub fn is_anim_enable(&self) -> bool {
+        self.literal_name == "lv_anim_enable_t"
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.literal_name == "lv_dir_t"
     }
 }
 
@@ -393,10 +439,12 @@ impl Rusty for LvType {
     fn code(&self, _parent: &Self::Parent) -> WrapperResult<TokenStream> {
         match TYPE_MAPPINGS.get(self.literal_name.as_str()) {
             Some(name) => {
-                let val = if self.is_str_arry() {
-                    quote!(*mut *const cty::c_char)
+                let val = if self.is_anim_enable() {
+                    quote!(crate::style::Animation)
+                } else if self.is_dir() {
+                    quote!(crate::style::Dir)
                 } else if self.is_str() {
-                    quote!(&cstr_core::CStr)
+                    quote!(&'static cstr_core::CStr)
                 } else {
                     let ident = format_ident!("{}", name);
                     if name.ends_with("_t") {
@@ -481,7 +529,7 @@ impl CodeGen {
 
         functions
             .iter()
-            .filter(|e| create_func.is_match(e.name.as_str()) && e.args.len() == 1)
+            .filter(|e| create_func.is_match(e.name.as_str()) && e.is_method())
             .map(|f| {
                 String::from(
                     create_func
@@ -517,6 +565,8 @@ impl CodeGen {
                 })
             })
             .filter(|ff| ff.sig.ident.to_string().starts_with(LIB_PREFIX))
+            // Variadic functions take printf formats, we can't call them safely.
+            .filter(|ff| ff.sig.variadic.is_none())
             .map(|ff| ff.into())
             .collect::<Vec<LvFunc>>();
 
@@ -555,59 +605,40 @@ mod test {
 
         let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
 
-        let ffn = cg.get(0).unwrap();
+        let ffn = cg.first().unwrap();
         assert_eq!(ffn.name, "lv_obj_get_screen");
         assert_eq!(ffn.args[0].name, "obj");
     }
 
     #[test]
     fn can_identify_widgets_from_function_names() {
+        let parent = || {
+            LvArg::new(
+                "parent".to_string(),
+                LvType::new("* mut lv_obj_t".to_string()),
+            )
+        };
         let funcs = vec![
+            LvFunc::new("lv_obj_create".to_string(), vec![parent()], None),
+            LvFunc::new("lv_btn_create".to_string(), vec![parent()], None),
             LvFunc::new(
-                "lv_obj_create".to_string(),
-                vec![
-                    LvArg::new("parent".to_string(), LvType::new("abc".to_string())),
-                    LvArg::new("copy_from".to_string(), LvType::new("bcf".to_string())),
-                ],
-                None,
-            ),
-            LvFunc::new(
-                "lv_btn_create".to_string(),
-                vec![
-                    LvArg::new("parent".to_string(), LvType::new("abc".to_string())),
-                    LvArg::new("copy_from".to_string(), LvType::new("bcf".to_string())),
-                ],
-                None,
-            ),
-            LvFunc::new(
-                "lv_do_something".to_string(),
+                "lv_spinner_create".to_string(),
                 vec![
-                    LvArg::new("parent".to_string(), LvType::new("abc".to_string())),
-                    LvArg::new("copy_from".to_string(), LvType::new("bcf".to_string())),
-                ],
-                None,
-            ),
-            LvFunc::new(
-                "lv_invalid_create".to_string(),
-                vec![LvArg::new(
-                    "parent".to_string(),
-                    LvType::new("abc".to_string()),
-                )],
-                None,
-            ),
-            LvFunc::new(
-                "lv_cb_create".to_string(),
-                vec![
-                    LvArg::new("parent".to_string(), LvType::new("abc".to_string())),
-                    LvArg::new("copy_from".to_string(), LvType::new("bcf".to_string())),
+                    parent(),
+                    LvArg::new("time".to_string(), LvType::new("u32".to_string())),
+                    LvArg::new("arc_length".to_string(), LvType::new("u32".to_string())),
                 ],
                 None,
             ),
+            LvFunc::new("lv_do_something".to_string(), vec![parent()], None),
+            // Not a widget, it has no parent
+            LvFunc::new("lv_group_create".to_string(), vec![], None),
         ];
 
-        let widget_names = CodeGen::get_widget_names(&funcs);
+        let mut widget_names = CodeGen::get_widget_names(&funcs);
+        widget_names.sort();
 
-        assert_eq!(widget_names.len(), 3);
+        assert_eq!(widget_names, ["btn", "obj", "spinner"]);
     }
 
     #[test]
@@ -628,11 +659,9 @@ mod test {
 
         let code = arc_set_bg_end_angle.code(&arc_widget).unwrap();
         let expected_code = quote! {
-            pub fn set_bg_end_angle(&mut self, end: u16) -> crate::LvResult<()> {
-                unsafe {
-                    lvgl_sys::lv_arc_set_bg_end_angle(self.core.raw().as_mut(), end);
-                }
-                Ok(())
+            pub fn set_bg_end_angle(&mut self, end: u16) -> &mut Self {
+                unsafe { lvgl_sys::lv_arc_set_bg_end_angle(&mut *self.raw, end) };
+                self
             }
         };
 
@@ -651,7 +680,7 @@ mod test {
         };
         let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
 
-        let label_set_text = cg.get(0).unwrap().clone();
+        let label_set_text = cg.first().unwrap().clone();
         let parent_widget = LvWidget {
             name: "label".to_string(),
             methods: vec![],
@@ -660,14 +689,11 @@ mod test {
         let code = label_set_text.code(&parent_widget).unwrap();
         let expected_code = quote! {
 
-            pub fn set_text(&mut self, text: &cstr_core::CStr) -> crate::LvResult<()> {
-                unsafe {
-                    lvgl_sys::lv_label_set_text(
-                        self.core.raw().as_mut(),
-                        text.as_ptr()
-                    );
-                }
-                Ok(())
+            pub fn set_text(&mut self, text: &str) -> &mut Self {
+                crate::support::with_cstr(text, |text| unsafe {
+                    lvgl_sys::lv_label_set_text(&mut *self.raw, text)
+                });
+                self
             }
 
         };
@@ -675,6 +701,44 @@ mod test {
         assert_eq!(code.to_string(), expected_code.to_string());
     }
 
+    #[test]
+    fn generate_method_wrapper_for_static_str_types_as_argument() {
+        let bindgen_code = quote! {
+            extern "C" {
+                pub fn lv_label_set_text_static(label: *mut lv_obj_t, text: *const cty::c_char);
+            }
+        };
+        let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
+
+        let label_set_text_static = cg.first().unwrap().clone();
+        let parent_widget = LvWidget {
+            name: "label".to_string(),
+            methods: vec![],
+        };
+
+        let code = label_set_text_static.code(&parent_widget).unwrap();
+        let expected_code = quote! {
+            pub fn set_text_static(&mut self, text: &'static cstr_core::CStr) -> &mut Self {
+                unsafe { lvgl_sys::lv_label_set_text_static(&mut *self.raw, text.as_ptr()) };
+                self
+            }
+        };
+
+        assert_eq!(code.to_string(), expected_code.to_string());
+    }
+
+    #[test]
+    fn skip_variadic_functions() {
+        let bindgen_code = quote! {
+            extern "C" {
+                pub fn lv_label_set_text_fmt(label: *mut lv_obj_t, fmt: *const cty::c_char, ...);
+            }
+        };
+        let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
+
+        assert!(cg.is_empty());
+    }
+
     #[test]
     fn generate_basic_widget_code() {
         let arc_widget = LvWidget {
@@ -686,7 +750,7 @@ mod test {
         let expected_code = quote! {
             define_object!(Arc);
 
-            impl Arc {
+            impl<C: 'static> Arc<C> {
 
             }
         };
@@ -696,17 +760,14 @@ mod test {
 
     #[test]
     fn generate_widget_with_constructor_code() {
-        // pub fn lv_arc_create(par: *mut lv_obj_t, copy: *const lv_obj_t) -> *mut lv_obj_t;
+        // pub fn lv_arc_create(parent: *mut lv_obj_t) -> *mut lv_obj_t;
         let arc_create = LvFunc::new(
             "lv_arc_create".to_string(),
-            vec![
-                LvArg::new("par".to_string(), LvType::new("*mut lv_obj_t".to_string())),
-                LvArg::new(
-                    "copy".to_string(),
-                    LvType::new("*const lv_obj_t".to_string()),
-                ),
-            ],
-            Some(LvType::new("*mut lv_obj_t".to_string())),
+            vec![LvArg::new(
+                "parent".to_string(),
+                LvType::new("* mut lv_obj_t".to_string()),
+            )],
+            Some(LvType::new("* mut lv_obj_t".to_string())),
         );
 
         let arc_widget = LvWidget {
@@ -718,17 +779,12 @@ mod test {
         let expected_code = quote! {
             define_object!(Arc);
 
-            impl Arc {
-                pub fn new<C>(parent: &mut C) -> crate::LvResult<Self>
-                where
-                    C: crate::core::NativeObject,
-                {
-
+            impl<C: 'static> Arc<C> {
+                pub fn new<'a>(parent: &mut impl crate::core::ObjExt<C>) -> Self {
                     unsafe {
-                        let ptr = lvgl_sys::lv_arc_create(parent.raw().as_mut());
-                        let raw = core::ptr::NonNull::new(ptr).except("OOM");
-                        let core = <crate::Obj as crate::Widget>::from_raw(raw);
-                        Ok(Self { core })
+                        let obj = lvgl_sys::lv_arc_create(&mut *parent.raw);
+                        let obj = Obj::from_raw(obj.as_mut().expect("OOM"));
+                        Self { obj }
                     }
                 }
             }
@@ -736,4 +792,76 @@ mod test {
 
         assert_eq!(code.to_string(), expected_code.to_string());
     }
+
+    #[test]
+    fn generate_constructor_with_arguments() {
+        let bindgen_code = quote! {
+            extern "C" {
+                pub fn lv_tabview_create(parent: *mut lv_obj_t, tab_pos: lv_dir_t, tab_size: lv_coord_t) -> *mut lv_obj_t;
+            }
+        };
+        let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
+
+        let tabview_create = cg.first().unwrap().clone();
+        let parent_widget = LvWidget {
+            name: "tabview".to_string(),
+            methods: vec![],
+        };
+
+        let code = tabview_create.code(&parent_widget).unwrap();
+        let expected_code = quote! {
+            pub fn new<'a>(
+                parent: &mut impl crate::core::ObjExt<C>,
+                tab_pos: crate::style::Dir,
+                tab_size: lvgl_sys::lv_coord_t
+            ) -> Self {
+                unsafe {
+                    let obj = lvgl_sys::lv_tabview_create(&mut *parent.raw, tab_pos.bits(), tab_size);
+                    let obj = Obj::from_raw(obj.as_mut().expect("OOM"));
+                    Self { obj }
+                }
+            }
+        };
+
+        assert_eq!(code.to_string(), expected_code.to_string());
+    }
+
+    #[test]
+    fn generate_constructor_with_str_arguments() {
+        let bindgen_code = quote! {
+            extern "C" {
+                pub fn lv_foo_create(parent: *mut lv_obj_t, title: *const cty::c_char) -> *mut lv_obj_t;
+            }
+        };
+        let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
+
+        let foo_create = cg.first().unwrap().clone();
+        let parent_widget = LvWidget {
+            name: "foo".to_string(),
+            methods: vec![],
+        };
+
+        let code = foo_create.code(&parent_widget).unwrap();
+        let expected_code = quote! {
+            pub fn new<'a>(parent: &mut impl crate::core::ObjExt<C>, title: &str) -> Self {
+                crate::support::with_cstr(title, |title| unsafe {
+                    let obj = lvgl_sys::lv_foo_create(&mut *parent.raw, title);
+                    let obj = Obj::from_raw(obj.as_mut().expect("OOM"));
+                    Self { obj }
+                })
+            }
+        };
+
+        assert_eq!(code.to_string(), expected_code.to_string());
+    }
+
+    #[test]
+    fn skip_hand_written_widgets() {
+        let msgbox_widget = LvWidget {
+            name: "msgbox".to_string(),
+            methods: vec![],
+        };
+
+        assert!(msgbox_widget.code(&()).is_err());
+    }
 }
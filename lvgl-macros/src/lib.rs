@@ -0,0 +1,141 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use std::path::{Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, LitStr, Token};
+
+// The formats that can be converted from RGBA pixels, without a palette.
+const FORMATS: &[&str] = &[
+    "TrueColor",
+    "TrueColorAlpha",
+    "Alpha1Bit",
+    "Alpha2Bit",
+    "Alpha4Bit",
+    "Alpha8Bit",
+];
+
+struct ImageArgs {
+    path: LitStr,
+    format: Ident,
+}
+
+impl Parse for ImageArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path: LitStr = input.parse()?;
+        let mut format = Ident::new("TrueColor", path.span());
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "format" {
+                return Err(syn::Error::new(key.span(), "expected `format = ...`"));
+            }
+            input.parse::<Token![=]>()?;
+            format = input.parse()?;
+            input.parse::<Option<Token![,]>>()?;
+        }
+        if !FORMATS.iter().any(|f| format == f) {
+            let msg = format!("unsupported format, expected one of {}", FORMATS.join(", "));
+            return Err(syn::Error::new(format.span(), msg));
+        }
+        Ok(Self { path, format })
+    }
+}
+
+/// Convert a PNG or BMP file to an `ImageDescriptor` at build time. The path
+/// is relative to the directory of `Cargo.toml`. The format is `TrueColor` by
+/// default, it can be any `ImageFormat` without a palette.
+/// ```ignore
+/// let logo = lvgl::include_image!("assets/logo.png", format = TrueColorAlpha);
+/// img.set_src(&logo);
+/// ```
+/// The pixels are in a static array, converted to the `LV_COLOR_DEPTH` of
+/// `lv_conf.h`. Only the descriptor pointing to them is made at runtime.
+#[proc_macro]
+pub fn include_image(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(input as ImageArgs);
+    let dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = Path::new(&dir).join(args.path.value());
+    expand(&path, &args.format)
+        .unwrap_or_else(|msg| syn::Error::new(args.path.span(), msg).to_compile_error())
+        .into()
+}
+
+fn expand(path: &Path, format: &Ident) -> Result<TokenStream, String> {
+    let image = image::open(path)
+        .map_err(|e| format!("can't read {}: {}", path.display(), e))?
+        .into_rgba8();
+    let (width, height) = image.dimensions();
+    if width >= 2048 || height >= 2048 {
+        return Err(format!(
+            "{} is too big, the maximum is 2047x2047",
+            path.display()
+        ));
+    }
+    // The formats depending on the color depth are converted by the lvgl crate.
+    let rgba = Literal::byte_string(image.as_raw());
+    let path = path_str(path)?;
+    Ok(quote! {
+        {
+            // Rebuild when the file changes.
+            const _: &[u8] = include_bytes!(#path);
+            const FORMAT: ::lvgl::widgets::ImageFormat = ::lvgl::widgets::ImageFormat::#format;
+            // lvgl reads the colors in place, aligned like lv_color32_t at most.
+            #[repr(C, align(4))]
+            struct Aligned([u8; FORMAT.data_size(#width, #height)]);
+            static DATA: Aligned = Aligned(FORMAT.convert_rgba(#rgba, #width));
+            ::lvgl::widgets::ImageDescriptor::from_static(&DATA.0, #width, #height, FORMAT)
+        }
+    })
+}
+
+fn path_str(path: &Path) -> Result<String, String> {
+    let path: PathBuf = path.canonicalize().map_err(|e| e.to_string())?;
+    path.to_str()
+        .map(String::from)
+        .ok_or_else(|| format!("{} isn't valid UTF-8", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{expand, ImageArgs};
+    use image::{Rgba, RgbaImage};
+    use proc_macro2::Span;
+    use syn::Ident;
+
+    #[test]
+    fn parse_format() {
+        let args: ImageArgs = syn::parse_str(r#""logo.png", format = TrueColorAlpha"#).unwrap();
+        assert_eq!(args.path.value(), "logo.png");
+        assert_eq!(args.format, "TrueColorAlpha");
+
+        let args: ImageArgs = syn::parse_str(r#""logo.png""#).unwrap();
+        assert_eq!(args.format, "TrueColor");
+
+        assert!(syn::parse_str::<ImageArgs>(r#""logo.png", format = Indexed8Bit"#).is_err());
+        assert!(syn::parse_str::<ImageArgs>(r#""logo.png", size = TrueColor"#).is_err());
+    }
+
+    #[test]
+    fn expand_png() {
+        let path = std::env::temp_dir().join("lvgl-macros-test.png");
+        RgbaImage::from_pixel(3, 2, Rgba([1, 2, 3, 4]))
+            .save(&path)
+            .unwrap();
+
+        let code = expand(&path, &Ident::new("Alpha8Bit", Span::call_site()))
+            .unwrap()
+            .to_string();
+        assert!(code.contains("ImageFormat :: Alpha8Bit"));
+        assert!(code.contains("data_size (3u32 , 2u32)"));
+        assert!(code.contains("align (4)"));
+        assert!(code.contains("from_static (& DATA . 0"));
+        assert!(code.contains(r#"b"\x01\x02\x03\x04"#));
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn missing_file() {
+        let path = std::env::temp_dir().join("lvgl-macros-missing.png");
+        assert!(expand(&path, &Ident::new("TrueColor", Span::call_site())).is_err());
+    }
+}
@@ -60,6 +60,7 @@ fn main() {
     cfg.define("LV_CONF_INCLUDE_SIMPLE", Some("1"))
         .include(&vendor_src)
         .include(&vendor)
+        .include(&shims_dir)
         .warnings(false)
         .include(&lv_config_dir)
         .archiver("arm-none-eabi-ar") // To avoid "has no symbols" warnings
@@ -72,6 +73,8 @@ fn main() {
         lv_config_dir.to_str().unwrap(),
         "-I",
         vendor.to_str().unwrap(),
+        "-I",
+        shims_dir.to_str().unwrap(),
         "-fvisibility=default",
         "-fshort-enums",
     ];
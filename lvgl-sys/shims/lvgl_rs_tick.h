@@ -0,0 +1,21 @@
+#ifndef LVGL_RS_TICK_H
+#define LVGL_RS_TICK_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#include <stdint.h>
+
+/* Implemented by the `lvgl` crate. Use it in `lv_conf.h` with:
+ *   #define LV_TICK_CUSTOM 1
+ *   #define LV_TICK_CUSTOM_INCLUDE "lvgl_rs_tick.h"
+ *   #define LV_TICK_CUSTOM_SYS_TIME_EXPR (lvgl_rs_tick_get())
+ */
+uint32_t lvgl_rs_tick_get(void);
+
+#ifdef __cplusplus
+} /* extern "C" */
+#endif
+
+#endif /*LVGL_RS_TICK_H*/
@@ -59,8 +59,28 @@ fn generate_color_settings(out_path: &Path) {
     .unwrap();
 }
 
+// With LV_TICK_CUSTOM, lvgl reads the time itself and `lv_tick_inc()` doesn't exist.
+fn configure_ticks() {
+    println!("cargo:rustc-check-cfg=cfg(lv_tick_custom)");
+    if lvgl_sys::LV_TICK_CUSTOM != 0 {
+        println!("cargo:rustc-cfg=lv_tick_custom");
+    }
+}
+
+// Images can borrow the pixels of lvgl when lv_color_t has the layout of the
+// raw data of PixelColor: Rgb888 takes 3 bytes but lv_color32_t takes 4, and
+// BinaryColor packs 8 pixels in a byte.
+fn configure_colors() {
+    println!("cargo:rustc-check-cfg=cfg(lv_color_raw)");
+    if (lvgl_sys::LV_COLOR_DEPTH, lvgl_sys::LV_COLOR_16_SWAP) == (16, 0) {
+        println!("cargo:rustc-cfg=lv_color_raw");
+    }
+}
+
 fn main() {
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
     generate_widgets(&out_path);
     generate_color_settings(&out_path);
+    configure_ticks();
+    configure_colors();
 }
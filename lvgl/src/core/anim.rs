@@ -0,0 +1,352 @@
+use alloc::{boxed::Box, rc::Rc};
+use core::{cell::Cell, mem::MaybeUninit, ptr};
+
+use super::lvgl::with_context;
+use super::ObjExt;
+
+/// Pass to `Anim::set_repeat_count()` to repeat an animation forever.
+pub const ANIM_REPEAT_INFINITE: u16 = lvgl_sys::LV_ANIM_REPEAT_INFINITE as u16;
+
+/// How the values of an animation progress over time.
+#[derive(Debug, Clone, Copy)]
+pub enum AnimPath {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    Overshoot,
+    Bounce,
+    /// Jump to the end value when the animation ends
+    Step,
+}
+
+impl Default for AnimPath {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl From<AnimPath> for lvgl_sys::lv_anim_path_cb_t {
+    fn from(path: AnimPath) -> Self {
+        match path {
+            AnimPath::Linear => Some(lvgl_sys::lv_anim_path_linear),
+            AnimPath::EaseIn => Some(lvgl_sys::lv_anim_path_ease_in),
+            AnimPath::EaseOut => Some(lvgl_sys::lv_anim_path_ease_out),
+            AnimPath::EaseInOut => Some(lvgl_sys::lv_anim_path_ease_in_out),
+            AnimPath::Overshoot => Some(lvgl_sys::lv_anim_path_overshoot),
+            AnimPath::Bounce => Some(lvgl_sys::lv_anim_path_bounce),
+            AnimPath::Step => Some(lvgl_sys::lv_anim_path_step),
+        }
+    }
+}
+
+// The closures of an animation. Its address is the `var` of the lvgl animation,
+// which makes it unique, and lets the C callbacks find the closures.
+pub(crate) struct AnimCallbacks {
+    exec: Option<Box<dyn FnMut(i32)>>,
+    ready: Option<Box<dyn FnMut()>>,
+    deleted: Option<Box<dyn FnMut()>>,
+    // The animation is cancelled when this object is deleted.
+    target: *mut lvgl_sys::lv_obj_t,
+    // Shared with the handles, cleared when lvgl deletes the animation. The
+    // handles don't touch the closures afterwards, their address may be reused.
+    alive: Rc<Cell<bool>>,
+    // Set while a closure runs. If the animation is deleted meanwhile, e.g.
+    // cancelled from its exec closure, the closures are freed once it returns.
+    running: bool,
+    deleted_while_running: bool,
+}
+
+impl Default for AnimCallbacks {
+    fn default() -> Self {
+        Self {
+            exec: None,
+            ready: None,
+            deleted: None,
+            target: ptr::null_mut(),
+            alive: Rc::new(Cell::new(true)),
+            running: false,
+            deleted_while_running: false,
+        }
+    }
+}
+
+/// An animation, changing a value over time and passing it to a closure.
+/// The closures are freed when the animation ends or is cancelled. They only
+/// run when the animation progresses from `Lvgl::run_tasks()`.
+///
+/// Most of the setters of lv_anim are static inline functions that don't get
+/// exported, so we write the fields directly.
+pub struct Anim {
+    raw: lvgl_sys::lv_anim_t,
+    callbacks: Box<AnimCallbacks>,
+}
+
+impl Anim {
+    pub fn new() -> Self {
+        let raw = unsafe {
+            let mut raw = MaybeUninit::<lvgl_sys::lv_anim_t>::uninit();
+            lvgl_sys::lv_anim_init(raw.as_mut_ptr());
+            raw.assume_init()
+        };
+        Self {
+            raw,
+            callbacks: Box::new(AnimCallbacks::default()),
+        }
+    }
+
+    /// Set the start and end values of the animation.
+    pub fn set_values(&mut self, start: i32, end: i32) -> &mut Self {
+        self.raw.start_value = start;
+        self.raw.current_value = start;
+        self.raw.end_value = end;
+        self
+    }
+
+    /// Set the duration of the animation, in milliseconds.
+    pub fn set_time(&mut self, duration: u32) -> &mut Self {
+        self.raw.time = duration as i32;
+        self
+    }
+
+    /// Wait before starting the animation, in milliseconds.
+    pub fn set_delay(&mut self, delay: u32) -> &mut Self {
+        self.raw.act_time = -(delay as i32);
+        self
+    }
+
+    pub fn set_path(&mut self, path: AnimPath) -> &mut Self {
+        self.raw.path_cb = path.into();
+        self
+    }
+
+    /// Play the animation backward when it's ready, in milliseconds.
+    /// Zero disables the playback.
+    pub fn set_playback_time(&mut self, time: u32) -> &mut Self {
+        self.raw.playback_time = time;
+        self
+    }
+
+    /// Wait before playing the animation backward, in milliseconds.
+    pub fn set_playback_delay(&mut self, delay: u32) -> &mut Self {
+        self.raw.playback_delay = delay;
+        self
+    }
+
+    /// How many times the animation is played. See `ANIM_REPEAT_INFINITE`.
+    pub fn set_repeat_count(&mut self, count: u16) -> &mut Self {
+        self.raw.repeat_cnt = count;
+        self
+    }
+
+    /// Wait before repeating the animation, in milliseconds.
+    pub fn set_repeat_delay(&mut self, delay: u32) -> &mut Self {
+        self.raw.repeat_delay = delay;
+        self
+    }
+
+    /// Animate a property of `obj`: the animation is cancelled when the object
+    /// is deleted, so the exec closure doesn't reach a deleted object. This is
+    /// ignored by `AnimTimeline`, which owns the closures of its animations.
+    pub fn set_target<C>(&mut self, obj: &mut impl ObjExt<C>) -> &mut Self {
+        self.callbacks.target = &mut *obj.raw;
+        self
+    }
+
+    /// Register the closure applying the current value of the animation.
+    pub fn on_exec<C: 'static>(&mut self, mut f: impl FnMut(&mut C, i32) + 'static) -> &mut Self {
+        self.callbacks.exec = Some(Box::new(move |value| {
+            with_context(|context| f(context, value));
+        }));
+        self
+    }
+
+    /// Register a callback, called when the animation is completed.
+    pub fn on_ready<C: 'static>(&mut self, mut f: impl FnMut(&mut C) + 'static) -> &mut Self {
+        self.callbacks.ready = Some(Box::new(move || {
+            with_context(|context| f(context));
+        }));
+        self
+    }
+
+    /// Register a callback, called when the animation is deleted, either
+    /// because it is completed or because it was cancelled.
+    pub fn on_deleted<C: 'static>(&mut self, mut f: impl FnMut(&mut C) + 'static) -> &mut Self {
+        self.callbacks.deleted = Some(Box::new(move || {
+            with_context(|context| f(context));
+        }));
+        self
+    }
+
+    /// Start the animation. The returned handle can be used to cancel it.
+    pub fn start(self) -> AnimHandle {
+        let (mut raw, callbacks) = self.into_raw();
+        // lvgl calls deleted_cb in every case, so this is where we free the closures.
+        raw.deleted_cb = Some(deleted_callback);
+        let alive = callbacks.alive.clone();
+        let target = callbacks.target;
+        let var = Box::into_raw(callbacks);
+        unsafe {
+            if let Some(target) = target.as_mut() {
+                lvgl_sys::lv_obj_add_event_cb(
+                    target,
+                    Some(target_deleted_callback),
+                    lvgl_sys::lv_event_code_t_LV_EVENT_DELETE,
+                    var as *mut cty::c_void,
+                );
+            }
+            lvgl_sys::lv_anim_start(&raw);
+        }
+        AnimHandle { var, alive }
+    }
+
+    // The returned animation refers to the closures, which must outlive all
+    // the copies lvgl makes of it.
+    pub(crate) fn into_raw(mut self) -> (lvgl_sys::lv_anim_t, Box<AnimCallbacks>) {
+        self.raw.var = self.callbacks.as_mut() as *mut AnimCallbacks as *mut cty::c_void;
+        self.raw.exec_cb = Some(exec_callback);
+        self.raw.ready_cb = Some(ready_callback);
+        (self.raw, self.callbacks)
+    }
+}
+
+impl Default for Anim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A handle on a started animation. It can be kept after the animation ends.
+pub struct AnimHandle {
+    var: *mut AnimCallbacks,
+    alive: Rc<Cell<bool>>,
+}
+
+impl AnimHandle {
+    /// Stop the animation before its end. Returns false if the animation has
+    /// already ended. It can be called from the closures of the animation,
+    /// they are freed once they return.
+    pub fn cancel(self) -> bool {
+        self.is_running()
+            && unsafe { lvgl_sys::lv_anim_del(self.var as *mut cty::c_void, Some(exec_callback)) }
+    }
+
+    /// Whether the animation is still running.
+    pub fn is_running(&self) -> bool {
+        self.alive.get()
+    }
+}
+
+// The closures are only borrowed field by field: a closure may delete the
+// animation, which flags the callbacks through the same pointer.
+unsafe extern "C" fn exec_callback(var: *mut cty::c_void, value: i32) {
+    let callbacks = var as *mut AnimCallbacks;
+    (*callbacks).running = true;
+    if let Some(exec) = (*callbacks).exec.as_mut() {
+        exec(value);
+    }
+    end_running(callbacks);
+}
+
+unsafe extern "C" fn ready_callback(anim: *mut lvgl_sys::lv_anim_t) {
+    let callbacks = (*anim).var as *mut AnimCallbacks;
+    (*callbacks).running = true;
+    if let Some(ready) = (*callbacks).ready.as_mut() {
+        ready();
+    }
+    end_running(callbacks);
+}
+
+unsafe fn end_running(callbacks: *mut AnimCallbacks) {
+    (*callbacks).running = false;
+    if (*callbacks).deleted_while_running {
+        free_callbacks(callbacks);
+    }
+}
+
+unsafe extern "C" fn deleted_callback(anim: *mut lvgl_sys::lv_anim_t) {
+    let callbacks = (*anim).var as *mut AnimCallbacks;
+    (*callbacks).alive.set(false);
+    if (*callbacks).running {
+        (*callbacks).deleted_while_running = true;
+    } else {
+        free_callbacks(callbacks);
+    }
+}
+
+unsafe fn free_callbacks(callbacks: *mut AnimCallbacks) {
+    let mut callbacks = Box::from_raw(callbacks);
+    if let Some(target) = callbacks.target.as_mut() {
+        let var = callbacks.as_mut() as *mut AnimCallbacks as *mut cty::c_void;
+        lvgl_sys::lv_obj_remove_event_cb_with_user_data(target, Some(target_deleted_callback), var);
+    }
+    if let Some(deleted) = callbacks.deleted.as_mut() {
+        deleted();
+    }
+}
+
+unsafe extern "C" fn target_deleted_callback(event: *mut lvgl_sys::lv_event_t) {
+    let var = lvgl_sys::lv_event_get_user_data(event);
+    // The event goes away with the object, it must not be removed while it
+    // is being sent.
+    (*(var as *mut AnimCallbacks)).target = ptr::null_mut();
+    lvgl_sys::lv_anim_del(var, Some(exec_callback));
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    use crate::core::{Anim, AnimHandle, Lvgl};
+    use crate::support::test::with_screen;
+    use crate::widgets::Container;
+
+    #[test]
+    fn deleting_the_target_cancels() {
+        with_screen(|screen| {
+            let mut obj = Container::new(screen);
+            let freed = Rc::new(());
+            let captured = freed.clone();
+            let mut anim = Anim::new();
+            anim.set_values(0, 100)
+                .set_time(1000)
+                .set_target(&mut obj)
+                .on_exec(move |_: &mut (), _value| {
+                    let _ = &captured;
+                });
+            let handle = anim.start();
+            assert!(handle.is_running());
+
+            unsafe { lvgl_sys::lv_obj_del(&mut *obj.raw) };
+            assert!(!handle.is_running());
+            assert_eq!(Rc::strong_count(&freed), 1);
+            assert!(!handle.cancel());
+        });
+    }
+
+    #[cfg(not(lv_tick_custom))]
+    #[test]
+    fn cancel_from_exec() {
+        with_screen(|_screen| {
+            let mut lvgl = Lvgl::new();
+            let deleted = Rc::new(Cell::new(false));
+            let on_deleted = deleted.clone();
+            let mut anim = Anim::new();
+            anim.set_values(0, 100)
+                .set_time(1000)
+                .on_exec(|handle: &mut Option<AnimHandle>, _value| {
+                    assert!(handle.take().unwrap().cancel());
+                })
+                .on_deleted(move |_: &mut Option<AnimHandle>| on_deleted.set(true));
+
+            let mut context = Some(anim.start());
+            lvgl.ticks().inc(100);
+            lvgl.run_tasks(&mut context);
+            assert!(context.is_none());
+            // The deleted closure ran once the exec closure returned.
+            assert!(deleted.get());
+            assert_eq!(Rc::strong_count(&deleted), 1);
+        });
+    }
+}
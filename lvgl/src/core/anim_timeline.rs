@@ -0,0 +1,87 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use super::{Anim, AnimCallbacks};
+
+/// Pass to `AnimTimeline::set_progress()` to seek to the end of the timeline.
+pub const ANIM_TIMELINE_PROGRESS_MAX: u16 = lvgl_sys::LV_ANIM_TIMELINE_PROGRESS_MAX as u16;
+
+/// A timeline plays several animations, each one starting at its own offset.
+/// It can be played backward, or seeked to a given progress.
+pub struct AnimTimeline {
+    pub raw: &'static mut lvgl_sys::lv_anim_timeline_t,
+    // The timeline and the animations it starts refer to the closures.
+    callbacks: Vec<Box<AnimCallbacks>>,
+}
+
+impl AnimTimeline {
+    pub fn new() -> Self {
+        let raw = unsafe { lvgl_sys::lv_anim_timeline_create().as_mut().expect("OOM") };
+        Self {
+            raw,
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Add an animation, starting `start_time` milliseconds after the start of
+    /// the timeline. The delay of the animation is ignored. Its `on_deleted`
+    /// callback is never called, the closures are freed with the timeline.
+    pub fn add(&mut self, start_time: u32, anim: Anim) -> &mut Self {
+        let (mut raw, callbacks) = anim.into_raw();
+        unsafe { lvgl_sys::lv_anim_timeline_add(&mut *self.raw, start_time, &mut raw) };
+        self.callbacks.push(callbacks);
+        self
+    }
+
+    /// Start playing the timeline, from its current progress. Returns the
+    /// total duration of the timeline, in milliseconds.
+    pub fn start(&mut self) -> u32 {
+        unsafe { lvgl_sys::lv_anim_timeline_start(&mut *self.raw) }
+    }
+
+    /// Stop the animations of the timeline, where they are.
+    pub fn stop(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_anim_timeline_stop(&mut *self.raw) };
+        self
+    }
+
+    /// Play the timeline backward on the next `start()`.
+    pub fn set_reverse(&mut self, reverse: bool) -> &mut Self {
+        unsafe { lvgl_sys::lv_anim_timeline_set_reverse(&mut *self.raw, reverse) };
+        self
+    }
+
+    pub fn get_reverse(&self) -> bool {
+        unsafe { lvgl_sys::lv_anim_timeline_get_reverse(self.raw_mut_const()) }
+    }
+
+    /// Apply the values of all the animations at the given progress, between 0
+    /// and `ANIM_TIMELINE_PROGRESS_MAX`.
+    pub fn set_progress(&mut self, progress: u16) -> &mut Self {
+        unsafe { lvgl_sys::lv_anim_timeline_set_progress(&mut *self.raw, progress) };
+        self
+    }
+
+    /// The total duration of the timeline, in milliseconds.
+    pub fn get_playtime(&self) -> u32 {
+        unsafe { lvgl_sys::lv_anim_timeline_get_playtime(self.raw_mut_const()) }
+    }
+
+    // The getters of lv_anim_timeline aren't declared const, even though they
+    // don't modify the timeline.
+    fn raw_mut_const(&self) -> *mut lvgl_sys::lv_anim_timeline_t {
+        &*self.raw as *const _ as *mut _
+    }
+}
+
+impl Default for AnimTimeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for AnimTimeline {
+    fn drop(&mut self) {
+        // This stops the animations, so the closures can be freed afterwards.
+        unsafe { lvgl_sys::lv_anim_timeline_del(&mut *self.raw) };
+    }
+}
@@ -15,6 +15,13 @@ use embedded_graphics::{draw_target::DrawTarget, prelude::*, primitives::Rectang
 // This gives us "pub type PixelColor = embedded_graphics_core::pixel_color::Rgb565;" with the right color
 include!(concat!(env!("OUT_DIR"), "/generated-color-settings.rs"));
 
+// PixelColor has the memory layout of lv_color_t, the flush callback relies on it as well.
+const _: () = assert!(mem::size_of::<PixelColor>() == mem::size_of::<lvgl_sys::lv_color_t>());
+
+pub(crate) fn lv_color(color: PixelColor) -> lvgl_sys::lv_color_t {
+    unsafe { mem::transmute_copy(&color) }
+}
+
 /// `Display` represents a display for Lvgl
 /// Limitations:
 /// * No async drawing, no double buffering
@@ -107,6 +114,8 @@ impl<T: DrawTarget<Color = PixelColor> + OriginDimensions> Display<T> {
         // Indicate to LVGL that we are ready with the flushing
         // Note that we could do something async if we were to use something like DMA and two buffers.
         lvgl_sys::lv_disp_flush_ready(disp_drv);
+        #[cfg(feature = "async")]
+        super::wake();
     }
 }
 
@@ -116,6 +125,14 @@ impl<T> Display<T> {
             lvgl_sys::lv_disp_load_scr(&mut *screen.raw);
         }
     }
+
+    /// Whether LVGL is waiting for the display to be done with the draw buffer.
+    pub fn is_flushing(&self) -> bool {
+        unsafe {
+            let draw_buf = (*self.disp.driver).draw_buf;
+            ptr::read_volatile(&(*draw_buf).flushing) != 0
+        }
+    }
 }
 
 impl<T> Deref for Display<T> {
@@ -2,6 +2,14 @@ use alloc::boxed::Box;
 use lvgl_sys::lv_obj_t;
 
 use core::convert::TryInto;
+#[cfg(feature = "async")]
+use alloc::{collections::VecDeque, rc::Rc};
+#[cfg(feature = "async")]
+use core::{
+    cell::RefCell,
+    future::poll_fn,
+    task::{Poll, Waker},
+};
 
 crate::native_enum! {
     lvgl_sys::lv_event_code_t,
@@ -112,7 +120,11 @@ crate::native_enum! {
     }
 }
 
-
+/// Run `cb` on the `event` of the object, or on all of its events. The
+/// callbacks lending the application context with `with_context()` don't get
+/// it when the event fires outside of `Lvgl::run_tasks()`, or from within
+/// another callback, e.g. `ValueChanged` fired by a `set_value()`: the context
+/// is already lent.
 pub(crate) fn add_event_cb<F>(obj_raw: &mut lv_obj_t, event: Option<Event>, cb: F)
 where
     F: FnMut(Event, &mut lv_obj_t, &mut lv_obj_t) + 'static
@@ -135,6 +147,99 @@ where
     }
 }
 
+/// Keep `data` alive until the object is deleted, for the data that lvgl
+/// refers to instead of copying it.
+pub(crate) fn keep_alive<T: 'static>(obj_raw: &mut lv_obj_t, data: T) {
+    let mut data = Some(data);
+    add_event_cb(obj_raw, Some(Event::Delete), move |_e, _current_target, _child| {
+        drop(data.take());
+    });
+}
+
+/// The data of type `T` kept by the object, inserted by `init` the first time
+/// and dropped when the object is deleted. There's one slot per type, found by
+/// its callback.
+pub(crate) fn event_slot<T: 'static>(obj_raw: &mut lv_obj_t, init: impl FnOnce() -> T) -> &mut T {
+    unsafe {
+        let slot = lvgl_sys::lv_obj_get_event_user_data(obj_raw, Some(drop_slot_cb::<T>)) as *mut T;
+        if let Some(slot) = slot.as_mut() {
+            return slot;
+        }
+        let slot = Box::into_raw(Box::new(init()));
+        lvgl_sys::lv_obj_add_event_cb(
+            obj_raw,
+            Some(drop_slot_cb::<T>),
+            lvgl_sys::lv_event_code_t_LV_EVENT_DELETE,
+            slot as *mut cty::c_void,
+        );
+        &mut *slot
+    }
+}
+
+unsafe extern "C" fn drop_slot_cb<T>(event: *mut lvgl_sys::lv_event_t) {
+    let slot = lvgl_sys::lv_event_get_user_data(event) as *mut T;
+    if !slot.is_null() {
+        drop(Box::from_raw(slot));
+    }
+}
+
+/// Receives the events of an object, see `ObjExt::on_event_async()`.
+/// The events are queued until they are received.
+#[cfg(feature = "async")]
+pub struct EventReceiver {
+    queue: Rc<RefCell<EventQueue>>,
+}
+
+#[cfg(feature = "async")]
+#[derive(Default)]
+struct EventQueue {
+    events: VecDeque<Event>,
+    waker: Option<Waker>,
+}
+
+#[cfg(feature = "async")]
+impl EventReceiver {
+    pub(crate) fn new(obj_raw: &mut lv_obj_t, event: Option<Event>) -> Self {
+        let queue = Rc::new(RefCell::new(EventQueue::default()));
+        let sender = queue.clone();
+        add_event_cb(obj_raw, event, move |e, _current_target, _child| {
+            // Don't queue events that nobody will receive.
+            if Rc::strong_count(&sender) == 1 {
+                return;
+            }
+            let waker = {
+                let mut queue = sender.borrow_mut();
+                queue.events.push_back(e);
+                queue.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+        Self { queue }
+    }
+
+    /// Wait for the next event.
+    pub async fn recv(&mut self) -> Event {
+        poll_fn(|cx| {
+            let mut queue = self.queue.borrow_mut();
+            match queue.events.pop_front() {
+                Some(e) => Poll::Ready(e),
+                None => {
+                    queue.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    /// Get the next event, if there is one already.
+    pub fn try_recv(&mut self) -> Option<Event> {
+        self.queue.borrow_mut().events.pop_front()
+    }
+}
+
 unsafe extern "C" fn event_callback(event: *mut lvgl_sys::lv_event_t)
 {
     // Seems a bit silly to use functions to access fields, but that's what the
@@ -0,0 +1,178 @@
+use alloc::boxed::Box;
+use core::{mem, ptr};
+use lvgl_sys::lv_obj_t;
+
+use super::{lvgl::with_context, Indev, ObjExt};
+
+/// A group of objects that can be navigated with a keypad or an encoder.
+/// Only one object of a group is focused at a time, and the keys sent by the
+/// input devices bound to the group are delivered to the focused object.
+pub struct Group {
+    pub raw: &'static mut lvgl_sys::lv_group_t,
+    // Double boxed so that we can hand a thin pointer to lvgl.
+    focus_cb: Option<Box<Box<dyn FnMut(&mut lv_obj_t)>>>,
+}
+
+impl Group {
+    /// Create an empty group.
+    pub fn new() -> Self {
+        let raw = unsafe { lvgl_sys::lv_group_create().as_mut().expect("OOM") };
+        Self {
+            raw,
+            focus_cb: None,
+        }
+    }
+
+    /// Make this group the default one. Objects that can be focused (buttons,
+    /// sliders, etc.) are automatically added to the default group when created.
+    pub fn set_default(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_set_default(&mut *self.raw) };
+        self
+    }
+
+    pub fn add_obj<C: 'static>(&mut self, obj: &mut impl ObjExt<C>) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_add_obj(&mut *self.raw, &mut *obj.raw) };
+        self
+    }
+
+    /// Remove an object from the group. This is a no-op if the object is not
+    /// part of this group.
+    pub fn remove_obj<C: 'static>(&mut self, obj: &mut impl ObjExt<C>) -> &mut Self {
+        let group = unsafe { lvgl_sys::lv_obj_get_group(&*obj.raw) } as *const lvgl_sys::lv_group_t;
+        if ptr::eq(group, &*self.raw) {
+            unsafe { lvgl_sys::lv_group_remove_obj(&mut *obj.raw) };
+        }
+        self
+    }
+
+    pub fn remove_all_objs(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_remove_all_objs(&mut *self.raw) };
+        self
+    }
+
+    /// Focus an object of the group, and defocus the previously focused one.
+    pub fn focus_obj<C: 'static>(&mut self, obj: &mut impl ObjExt<C>) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_focus_obj(&mut *obj.raw) };
+        self
+    }
+
+    pub fn focus_next(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_focus_next(&mut *self.raw) };
+        self
+    }
+
+    pub fn focus_prev(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_focus_prev(&mut *self.raw) };
+        self
+    }
+
+    /// Prevent the focus from changing.
+    pub fn focus_freeze(&mut self, freeze: bool) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_focus_freeze(&mut *self.raw, freeze) };
+        self
+    }
+
+    /// In editing mode, an encoder modifies the focused object (e.g. the value
+    /// of a slider) instead of moving the focus.
+    pub fn set_editing(&mut self, edit: bool) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_set_editing(&mut *self.raw, edit) };
+        self
+    }
+
+    pub fn get_editing(&self) -> bool {
+        unsafe { lvgl_sys::lv_group_get_editing(&*self.raw) }
+    }
+
+    /// Whether the focus moves back to the first object after the last one
+    /// (and vice versa).
+    pub fn set_wrap(&mut self, wrap: bool) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_set_wrap(&mut *self.raw, wrap) };
+        self
+    }
+
+    pub fn get_wrap(&self) -> bool {
+        unsafe { lvgl_sys::lv_group_get_wrap(self.raw_mut_const()) }
+    }
+
+    pub fn get_obj_count(&self) -> u32 {
+        unsafe { lvgl_sys::lv_group_get_obj_count(self.raw_mut_const()) }
+    }
+
+    // Some getters of lv_group aren't declared const, even though they don't
+    // modify the group.
+    fn raw_mut_const(&self) -> *mut lvgl_sys::lv_group_t {
+        &*self.raw as *const _ as *mut _
+    }
+
+    /// Send a key to the focused object, as if it came from a keypad.
+    pub fn send_key(&mut self, key: Key) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_send_data(&mut *self.raw, key.into()) };
+        self
+    }
+
+    /// Register a callback, called when a new object is focused. The callback
+    /// receives the newly focused object. Replaces any previous callback.
+    /// The callback only runs when the focus changes from `Lvgl::run_tasks()`.
+    pub fn on_focus<C: 'static>(
+        &mut self,
+        mut f: impl FnMut(&mut C, &mut lv_obj_t) + 'static,
+    ) -> &mut Self {
+        let mut cb: Box<Box<dyn FnMut(&mut lv_obj_t)>> = Box::new(Box::new(move |obj| {
+            with_context(|context| f(context, obj));
+        }));
+        unsafe {
+            self.raw.user_data = mem::transmute(cb.as_mut() as *mut Box<dyn FnMut(&mut lv_obj_t)>);
+            lvgl_sys::lv_group_set_focus_cb(&mut *self.raw, Some(focus_callback));
+        }
+        self.focus_cb = Some(cb);
+        self
+    }
+
+    /// Bind an input device to this group. Its keys will be sent to the
+    /// focused object.
+    pub fn bind_input_device(&mut self, indev: &mut Indev) -> &mut Self {
+        unsafe { lvgl_sys::lv_indev_set_group(indev.raw.as_ptr(), &mut *self.raw) };
+        self
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Group {
+    fn drop(&mut self) {
+        // This also unbinds the input devices that were using the group.
+        unsafe { lvgl_sys::lv_group_del(&mut *self.raw) };
+    }
+}
+
+unsafe extern "C" fn focus_callback(group: *mut lvgl_sys::lv_group_t) {
+    let group = group.as_mut().unwrap();
+    let focused = lvgl_sys::lv_group_get_focused(group);
+    let user_data: *mut Box<dyn FnMut(&mut lv_obj_t)> = mem::transmute(group.user_data);
+    if let (Some(closure), Some(focused)) = (user_data.as_mut(), focused.as_mut()) {
+        closure(focused);
+    }
+}
+
+crate::native_enum! {
+    u32,
+    /// Special keys understood by the objects of a group.
+    pub enum Key {
+        Up = lvgl_sys::LV_KEY_UP,
+        Down = lvgl_sys::LV_KEY_DOWN,
+        Right = lvgl_sys::LV_KEY_RIGHT,
+        Left = lvgl_sys::LV_KEY_LEFT,
+        Esc = lvgl_sys::LV_KEY_ESC,
+        Del = lvgl_sys::LV_KEY_DEL,
+        Backspace = lvgl_sys::LV_KEY_BACKSPACE,
+        Enter = lvgl_sys::LV_KEY_ENTER,
+        Next = lvgl_sys::LV_KEY_NEXT,
+        Prev = lvgl_sys::LV_KEY_PREV,
+        Home = lvgl_sys::LV_KEY_HOME,
+        End = lvgl_sys::LV_KEY_END,
+    }
+}
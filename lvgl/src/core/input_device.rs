@@ -1,6 +1,14 @@
-use core::mem::{self, MaybeUninit};
+use core::{
+    cell::Cell,
+    mem::{self, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+};
 use alloc::boxed::Box;
-use super::Display;
+use embedded_graphics::prelude::Point;
+use lvgl_sys::lv_obj_t;
+use super::{Display, Key, ObjExt};
+use crate::style::Dir;
 
 //////////////////
 // Generic trait
@@ -13,13 +21,14 @@ pub trait InputDeviceState {
 
 pub struct InputDevice<S> {
     state: Box<S>,
+    indev: Indev,
 }
 
 impl<S: InputDeviceState + Default> InputDevice<S> {
     pub fn new<D>(display: &mut Display<D>) -> Self {
         let mut state = Box::new(S::default());
 
-        unsafe {
+        let indev = unsafe {
             let mut indev_drv = {
                 let mut indev_drv = MaybeUninit::<lvgl_sys::lv_indev_drv_t>::uninit();
                 lvgl_sys::lv_indev_drv_init(indev_drv.as_mut_ptr());
@@ -30,13 +39,15 @@ impl<S: InputDeviceState + Default> InputDevice<S> {
                 indev_drv.user_data = mem::transmute(state.as_mut());
                 indev_drv
             };
-            let _indev = lvgl_sys::lv_indev_drv_register(indev_drv.as_mut());
+            let indev = lvgl_sys::lv_indev_drv_register(indev_drv.as_mut());
 
             // lvgl needs indev_drv to stick around
             Box::into_raw(indev_drv);
-        }
 
-        Self { state }
+            Indev { raw: NonNull::new(indev).expect("OOM") }
+        };
+
+        Self { state, indev }
     }
 
     // We could add a feature to run a user-provided closure when lvgl polls the
@@ -55,17 +66,115 @@ impl<S: InputDeviceState + Default> InputDevice<S> {
         state.populate_lv_indev_data(data);
     }
 
+    /// Access the state reported to LVGL. With the `async` feature, this
+    /// also wakes up `run()` so that the new state is read right away.
     pub fn state(&mut self) -> &mut S {
+        #[cfg(feature = "async")]
+        super::wake();
         self.state.as_mut()
     }
 }
 
+impl<S> Deref for InputDevice<S> {
+    type Target = Indev;
+
+    fn deref(&self) -> &Self::Target {
+        &self.indev
+    }
+}
+
+impl<S> DerefMut for InputDevice<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.indev
+    }
+}
+
 impl<S> Drop for InputDevice<S> {
     fn drop(&mut self) {
         panic!("InputDevice can't be dropped");
     }
 }
 
+//////////////////
+// Input device handle
+//////////////////
+
+/// A handle on an input device registered in lvgl. Several handles can
+/// refer to the same device, see `Indev::active()`.
+pub struct Indev {
+    // pub so that the user can use lvgl_sys functions directly. It's not a
+    // reference, as the handles of a device alias each other.
+    pub raw: NonNull<lvgl_sys::lv_indev_t>,
+}
+
+impl Indev {
+    /// The input device currently being processed by lvgl. This is only
+    /// available from event callbacks, e.g. to get the direction of a gesture.
+    pub fn active() -> Option<Self> {
+        NonNull::new(unsafe { lvgl_sys::lv_indev_get_act() }).map(|raw| Self { raw })
+    }
+
+    /// The object currently being pressed, if this device is the one being processed.
+    pub fn active_obj(&self) -> Option<&lv_obj_t> {
+        unsafe {
+            if lvgl_sys::lv_indev_get_act() == self.raw.as_ptr() {
+                lvgl_sys::lv_indev_get_obj_act().as_ref()
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Set an object to be used as cursor. Only pointer devices have a cursor.
+    /// The object is moved to the system layer and follows the pointer.
+    pub fn set_cursor<C: 'static>(&mut self, cursor: &mut impl ObjExt<C>) -> &mut Self {
+        unsafe { lvgl_sys::lv_indev_set_cursor(self.raw.as_ptr(), &mut *cursor.raw) };
+        self
+    }
+
+    /// The last point of a pointer device.
+    pub fn get_point(&self) -> Point {
+        let mut point = lvgl_sys::lv_point_t::default();
+        unsafe { lvgl_sys::lv_indev_get_point(self.raw.as_ptr(), &mut point) };
+        Point::new(point.x as i32, point.y as i32)
+    }
+
+    /// The movement vector of a pointer device, since the last read.
+    pub fn get_vect(&self) -> Point {
+        let mut point = lvgl_sys::lv_point_t::default();
+        unsafe { lvgl_sys::lv_indev_get_vect(self.raw.as_ptr(), &mut point) };
+        Point::new(point.x as i32, point.y as i32)
+    }
+
+    /// The direction of the current gesture of a pointer device.
+    pub fn gesture_dir(&self) -> Dir {
+        let dir = unsafe { lvgl_sys::lv_indev_get_gesture_dir(self.raw.as_ptr()) };
+        Dir::from_bits_truncate(dir)
+    }
+
+    /// The last pressed key of a keypad.
+    pub fn get_key(&self) -> u32 {
+        unsafe { lvgl_sys::lv_indev_get_key(self.raw.as_ptr()) }
+    }
+
+    /// Reset the state of the device, e.g. to ignore the current press.
+    pub fn reset(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_indev_reset(self.raw.as_ptr(), ptr::null_mut()) };
+        self
+    }
+
+    /// Wait for the release of the current press before sending new events.
+    pub fn wait_release(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_indev_wait_release(self.raw.as_ptr()) };
+        self
+    }
+
+    /// Disabled devices are not read by lvgl.
+    pub fn enable(&mut self, enable: bool) -> &mut Self {
+        unsafe { lvgl_sys::lv_indev_enable(self.raw.as_ptr(), enable) };
+        self
+    }
+}
 
 //////////////////
 // Touchpad
@@ -106,3 +215,85 @@ impl InputDeviceState for TouchPad {
         lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_POINTER
     }
 }
+
+
+//////////////////
+// Keypad
+//////////////////
+
+/// A keyboard or keypad. Bind it to a `Group` to navigate between objects.
+#[derive(Debug)]
+pub enum Keypad {
+    Released,
+    Pressed { key: u32 },
+}
+
+impl Keypad {
+    pub fn pressed(key: Key) -> Self {
+        Self::Pressed { key: key.into() }
+    }
+
+    /// A printable character, e.g. for a text area.
+    pub fn pressed_char(c: char) -> Self {
+        Self::Pressed { key: c as u32 }
+    }
+}
+
+impl Default for Keypad {
+    fn default() -> Self {
+        Self::Released
+    }
+}
+
+impl InputDeviceState for Keypad {
+    fn populate_lv_indev_data(&self, data: &mut lvgl_sys::lv_indev_data_t) {
+        match &self {
+            Keypad::Pressed { key } => {
+                data.key = *key;
+                data.state = lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED;
+            }
+            Keypad::Released => {
+                data.state = lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED;
+            }
+        }
+    }
+
+    fn input_device_type() -> lvgl_sys::lv_indev_type_t {
+        lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_KEYPAD
+    }
+}
+
+//////////////////
+// Encoder
+//////////////////
+
+/// A rotary encoder with a push button. Bind it to a `Group` to navigate
+/// between objects.
+#[derive(Debug, Default)]
+pub struct Encoder {
+    // The steps are accumulated until lvgl reads them.
+    diff: Cell<i16>,
+    pub pressed: bool,
+}
+
+impl Encoder {
+    /// Register rotation steps. Negative values rotate to the left.
+    pub fn rotate(&mut self, steps: i16) {
+        self.diff.set(self.diff.get().saturating_add(steps));
+    }
+}
+
+impl InputDeviceState for Encoder {
+    fn populate_lv_indev_data(&self, data: &mut lvgl_sys::lv_indev_data_t) {
+        data.enc_diff = self.diff.replace(0);
+        data.state = if self.pressed {
+            lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED
+        } else {
+            lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED
+        };
+    }
+
+    fn input_device_type() -> lvgl_sys::lv_indev_type_t {
+        lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_ENCODER
+    }
+}
@@ -1,6 +1,22 @@
 use core::{
+    any::TypeId,
     sync::atomic::{AtomicBool, Ordering},
     marker::PhantomData,
+    ptr::NonNull,
+    time::Duration,
+};
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use super::Timer;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{Receiver, Sender};
+#[cfg(feature = "async")]
+use super::Display;
+#[cfg(feature = "async")]
+use core::{
+    future::{poll_fn, Future},
+    task::Poll,
 };
 
 pub struct Lvgl {
@@ -8,8 +24,15 @@ pub struct Lvgl {
     // 1) Prevent the user from building that struct
     // 2) Remove the Send and Sync trait with the pointer
     _phantom: PhantomData<*mut cty::c_void>,
+    // Closures posted with a `UiHandle`, run from `run_tasks()`.
+    #[cfg(feature = "std")]
+    posted: (Sender<Posted>, Receiver<Posted>),
 }
 
+// A posted closure, with the type of the context it expects.
+#[cfg(feature = "std")]
+type Posted = (TypeId, Box<dyn FnOnce() + Send>);
+
 // We can let another thread use the tick_inc/timer_handler functions, but one
 // must be careful to not register buttons from another thread.
 unsafe impl Send for Lvgl {}
@@ -24,7 +47,11 @@ pub(crate) fn ensure_init() {
 impl Lvgl {
     pub fn new() -> Self {
         ensure_init();
-        Self { _phantom: PhantomData }
+        Self {
+            _phantom: PhantomData,
+            #[cfg(feature = "std")]
+            posted: std::sync::mpsc::channel(),
+        }
     }
 
     #[cfg(feature = "logger")]
@@ -50,13 +77,101 @@ impl Lvgl {
         }
     }
 
+    #[cfg(not(lv_tick_custom))]
     pub fn ticks(&self) -> Ticks {
         Ticks::new()
     }
 
-    /// Call this at least every few milliseconds to run LVGL tasks
-    pub fn run_tasks(&mut self) {
-        unsafe { lvgl_sys::lv_timer_handler(); }
+    /// Like `ticks()`, but measures the elapsed time itself.
+    #[cfg(all(feature = "std", not(lv_tick_custom)))]
+    pub fn std_ticks(&self) -> StdTicks {
+        StdTicks::new()
+    }
+
+    /// Use `clock` as the time source of LVGL, instead of incrementing ticks.
+    /// `clock` returns a monotonic time in milliseconds, wrapping on overflow.
+    /// This requires the following configuration in `lv_conf.h`:
+    /// ```c
+    /// #define LV_TICK_CUSTOM 1
+    /// #define LV_TICK_CUSTOM_INCLUDE "lvgl_rs_tick.h"
+    /// #define LV_TICK_CUSTOM_SYS_TIME_EXPR (lvgl_rs_tick_get())
+    /// ```
+    #[cfg(lv_tick_custom)]
+    pub fn set_tick_source(&mut self, clock: fn() -> u32) {
+        TICK_SOURCE.store(clock as usize, Ordering::Relaxed);
+    }
+
+    /// Call this at least every few milliseconds to run LVGL tasks.
+    /// The context is lent to the callbacks (events, timers, animations...)
+    /// for the duration of the call. Callbacks must expect a context of type
+    /// `C`, this is checked by a debug assertion.
+    /// Returns the time until the next timer is due, so the caller can sleep
+    /// until then. Input devices are read by a timer as well. Returns `None`
+    /// when no timer is running, e.g. when they are all paused: the caller
+    /// should then wait for something else, like input.
+    pub fn run_tasks<C: 'static>(&mut self, context: &mut C) -> Option<Duration> {
+        let next_due = unsafe {
+            CONTEXT = Some((NonNull::from(context).cast(), TypeId::of::<C>()));
+            #[cfg(feature = "std")]
+            self.run_posted::<C>();
+            let next_due = lvgl_sys::lv_timer_handler();
+            CONTEXT = None;
+            next_due
+        };
+        if next_due == lvgl_sys::LV_NO_TIMER_READY {
+            None
+        } else {
+            Some(Duration::from_millis(next_due.into()))
+        }
+    }
+
+    // Run the closures posted so far: the ones they post run on the next call,
+    // so that closures posting themselves again don't keep us here. The ones
+    // expecting another context are queued again, for the call lending it.
+    #[cfg(feature = "std")]
+    fn run_posted<C: 'static>(&mut self) {
+        let posted: Vec<Posted> = self.posted.1.try_iter().collect();
+        for (type_id, f) in posted {
+            if type_id == TypeId::of::<C>() {
+                f();
+            } else {
+                // Can't fail, we hold the receiver.
+                let _ = self.posted.0.send((type_id, f));
+            }
+        }
+    }
+
+    /// Time elapsed since the last user activity, on any display.
+    pub fn idle_for(&self) -> Duration {
+        let millis = unsafe { lvgl_sys::lv_disp_get_inactive_time(core::ptr::null()) };
+        Duration::from_millis(millis.into())
+    }
+
+    /// Reset the inactivity time, as if the user interacted with the default
+    /// display. Useful to keep the screen on, e.g. on a notification.
+    pub fn trigger_activity(&mut self) {
+        unsafe { lvgl_sys::lv_disp_trig_activity(core::ptr::null_mut()) }
+    }
+
+    /// Get a handle that other threads can use to run closures on the UI
+    /// thread. See `UiHandle`.
+    #[cfg(feature = "std")]
+    pub fn ui_handle<C: 'static>(&self) -> UiHandle<C> {
+        UiHandle {
+            sender: self.posted.0.clone(),
+            _context: PhantomData,
+        }
+    }
+
+    /// Run a closure every `period` milliseconds, from `run_tasks()`.
+    pub fn create_timer<C: 'static>(
+        &mut self,
+        period: u32,
+        mut f: impl FnMut(&mut C) + 'static,
+    ) -> Timer {
+        Timer::new(period, Box::new(move || {
+            with_context(|context| f(context));
+        }))
     }
 }
 
@@ -66,10 +181,58 @@ impl Default for Lvgl {
     }
 }
 
+/// Runs closures on the UI thread, from `Lvgl::run_tasks()`. It can be cloned
+/// and sent to other threads, e.g. to show the readings of a sensor.
+/// The objects can't be sent across threads, so the closures reach them
+/// through the application context:
+/// ```ignore
+/// let ui = lvgl.ui_handle::<Ui>();
+/// thread::spawn(move || loop {
+///     let temperature = sensor.read();
+///     ui.post(move |ui| ui.label.set_text(&format!("{}°C", temperature)));
+/// });
+/// ```
+#[cfg(feature = "std")]
+pub struct UiHandle<C> {
+    sender: Sender<Posted>,
+    _context: PhantomData<fn(&mut C)>,
+}
+
+#[cfg(feature = "std")]
+impl<C: 'static> UiHandle<C> {
+    /// Queue a closure, run by the next call to `Lvgl::run_tasks()` with a
+    /// context of type `C`. The calls with a context of another type keep it
+    /// queued.
+    /// Returns false if the `Lvgl` instance is gone.
+    pub fn post(&self, f: impl FnOnce(&mut C) + Send + 'static) -> bool {
+        let posted = self.sender.send((
+            TypeId::of::<C>(),
+            Box::new(move || {
+                with_context(f);
+            }),
+        ));
+        #[cfg(feature = "async")]
+        wake();
+        posted.is_ok()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> Clone for UiHandle<C> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            _context: PhantomData,
+        }
+    }
+}
+
+#[cfg(not(lv_tick_custom))]
 pub struct Ticks {
     _phantom: PhantomData<()>,
 }
 
+#[cfg(not(lv_tick_custom))]
 impl Ticks {
     fn new() -> Self {
         Self { _phantom: PhantomData }
@@ -85,34 +248,189 @@ impl Ticks {
     }
 }
 
-/*
+/// Ticks measuring the elapsed time with `std::time::Instant`, so they can't
+/// drift with the jitter of the main loop.
+#[cfg(all(feature = "std", not(lv_tick_custom)))]
+pub struct StdTicks {
+    ticks: Ticks,
+    last_tick: std::time::Instant,
+}
 
-// The type here doesn't really matter. We don't know it in advance.  We use a
-// global variable as opposed to something in a struct, because we would
-// otherwise have to save an extra reference for each callback that we register.
-// This cost memory for no good reason as we _have_ to operate with a singleton anyways.
-// This is because the lvgl timer_handler() doesn't take any argument.
-static mut APP_STATE: *mut () = ptr::null_mut();
+#[cfg(all(feature = "std", not(lv_tick_custom)))]
+impl StdTicks {
+    fn new() -> Self {
+        Self {
+            ticks: Ticks::new(),
+            last_tick: std::time::Instant::now(),
+        }
+    }
 
-// This gives a lifetime to the app state reference.
-// it shouldn't not be kept for longer than the duration of the callback
-pub(crate) struct AppState<S> {
-    // No Send/Sync because the lifetime is bounded by timer_handler().
-    _phantom: PhantomData<*mut S>,
+    /// Inform LVGL about the time elapsed since the last call.
+    /// Call this at least as often as `Lvgl::run_tasks()`.
+    pub fn update(&mut self) {
+        let millis = self.last_tick.elapsed().as_millis() as u32;
+        // Only account for whole milliseconds, the remainder is kept for the next update.
+        self.last_tick += Duration::from_millis(millis.into());
+        self.ticks.inc(millis);
+    }
 }
 
-impl<S> AppState<S> {
-    pub(crate) fn from_callbacks() -> Self {
-        Self { _phantom: PhantomData }
+// Holds the `fn() -> u32` registered with `Lvgl::set_tick_source()`, zero when unset.
+#[cfg(lv_tick_custom)]
+static TICK_SOURCE: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+/// Called by LVGL through `LV_TICK_CUSTOM_SYS_TIME_EXPR`, see `Lvgl::set_tick_source()`.
+#[cfg(lv_tick_custom)]
+#[no_mangle]
+pub extern "C" fn lvgl_rs_tick_get() -> u32 {
+    match TICK_SOURCE.load(Ordering::Relaxed) {
+        0 => 0,
+        clock => {
+            let clock: fn() -> u32 = unsafe { core::mem::transmute(clock) };
+            clock()
+        }
     }
+}
 
-    pub(crate) fn as_mut(&mut self) -> &mut S {
-        unsafe {
-            let app_state: *mut S = mem::transmute(APP_STATE);
-            // This should always work. This is only used from our extern C callbacks.
-            app_state.as_mut().expect("APP_STATE accessed outside of timer_handler")
+// Wakes up `run()`. The flag remembers wake-ups that happen while `run()` is
+// busy running the tasks, so they aren't lost.
+#[cfg(feature = "async")]
+static WAKER: futures_util::task::AtomicWaker = futures_util::task::AtomicWaker::new();
+#[cfg(feature = "async")]
+static WOKEN: AtomicBool = AtomicBool::new(false);
+
+/// Make `run()` run the LVGL tasks now, instead of waiting for the next timer.
+/// Input devices and displays call this when their state changes.
+/// This function is safe to call in an interrupt context.
+#[cfg(feature = "async")]
+pub fn wake() {
+    WOKEN.store(true, Ordering::Release);
+    WAKER.wake();
+}
+
+/// Drive LVGL from an async executor. This never returns.
+/// The tasks run when a timer is due, or when `wake()` is called, e.g. on
+/// input device activity. In between, `sleep` is awaited with the time until
+/// the next timer, so that the executor can run other tasks (or sleep). When
+/// no timer is running, only `wake()` runs the tasks again.
+/// The rest of the application talks to the UI with `on_event_async()`.
+///
+/// LVGL must still be informed about time, with `Ticks` from an interrupt
+/// or with `Lvgl::set_tick_source()`.
+///
+/// ```ignore
+/// lvgl::core::run(&mut lvgl, &display, &mut ui, |d| Timer::after(d.into())).await;
+/// ```
+#[cfg(feature = "async")]
+pub async fn run<C: 'static, T, F>(
+    lvgl: &mut Lvgl,
+    display: &Display<T>,
+    context: &mut C,
+    mut sleep: impl FnMut(Duration) -> F,
+) where
+    F: Future<Output = ()>,
+{
+    loop {
+        let next_due = lvgl.run_tasks(context);
+
+        // The draw buffer can't be reused before the display is done with it.
+        poll_fn(|cx| {
+            WAKER.register(cx.waker());
+            if display.is_flushing() {
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        })
+        .await;
+
+        let sleep = next_due.map(&mut sleep);
+        futures_util::pin_mut!(sleep);
+        poll_fn(|cx| {
+            WAKER.register(cx.waker());
+            if WOKEN.swap(false, Ordering::AcqRel) {
+                return Poll::Ready(());
+            }
+            match sleep.as_mut().as_pin_mut() {
+                Some(sleep) => sleep.poll(cx),
+                None => Poll::Pending,
+            }
+        })
+        .await;
+    }
+}
+
+// The application context, lent to the callbacks for the duration of
+// `Lvgl::run_tasks()`. We use a global variable as opposed to something in a
+// struct, because we would otherwise have to save an extra reference for each
+// callback that we register. This cost memory for no good reason as we _have_
+// to operate with a singleton anyways. This is because the lvgl
+// timer_handler() doesn't take any argument.
+// The type id makes sure that callbacks don't get a context of the wrong type.
+static mut CONTEXT: Option<(NonNull<()>, TypeId)> = None;
+
+/// Run `f` with the context lent by `Lvgl::run_tasks()`. Returns `None` without
+/// running `f` when there is no context, e.g. when lvgl runs a callback
+/// outside of `run_tasks()`.
+/// The context is taken away while `f` runs, so that callbacks triggered from
+/// within `f` can't get a second mutable reference to it.
+/// A context of another type than `C` is a mistake of the application, which
+/// registered the callback with the wrong type. It's caught by a debug
+/// assertion, and `f` isn't run in release builds.
+pub(crate) fn with_context<C: 'static, R>(f: impl FnOnce(&mut C) -> R) -> Option<R> {
+    // Gives the context back, even if `f` panics.
+    struct Restore(Option<(NonNull<()>, TypeId)>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            unsafe { CONTEXT = self.0 };
         }
     }
+
+    let (context, type_id) = unsafe { CONTEXT }?;
+    debug_assert!(
+        type_id == TypeId::of::<C>(),
+        "the callback expects a context of type {}, not the one lent by run_tasks()",
+        core::any::type_name::<C>()
+    );
+    if type_id != TypeId::of::<C>() {
+        return None;
+    }
+
+    let _restore = Restore(unsafe { CONTEXT });
+    unsafe { CONTEXT = None };
+    Some(f(unsafe { context.cast::<C>().as_mut() }))
 }
 
-*/
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    #[test]
+    fn posted_closures() {
+        use crate::core::Lvgl;
+        use crate::support::test::with_screen;
+
+        with_screen(|_screen| {
+            let mut lvgl = Lvgl::new();
+            let ui = lvgl.ui_handle::<u32>();
+            let again = ui.clone();
+            ui.post(move |count| {
+                *count += 1;
+                again.post(|count| *count += 10);
+            });
+            lvgl.ui_handle::<i64>().post(|value| *value = -1);
+
+            // The closure posted again runs on the next call.
+            let mut count = 0;
+            lvgl.run_tasks(&mut count);
+            assert_eq!(count, 1);
+            lvgl.run_tasks(&mut count);
+            assert_eq!(count, 11);
+
+            // The closure expecting another context is still queued.
+            let mut value = 0;
+            lvgl.run_tasks(&mut value);
+            assert_eq!(value, -1);
+        });
+    }
+}
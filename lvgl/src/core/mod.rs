@@ -16,3 +16,18 @@ pub use input_device::*;
 
 mod screen;
 pub use screen::*;
+
+mod snapshot;
+pub use snapshot::*;
+
+mod group;
+pub use group::*;
+
+mod anim;
+pub use anim::*;
+
+mod anim_timeline;
+pub use anim_timeline::*;
+
+mod timer;
+pub use timer::*;
@@ -1,71 +1,84 @@
 use lvgl_sys::{lv_coord_t, lv_obj_t};
 
 use core::{
+    marker::PhantomData,
     ops::{Deref, DerefMut},
-    ptr,
 };
 
 use crate::{
     core::event::add_event_cb,
-    core::Event,
+    core::lvgl::with_context,
+    core::{Event, Snapshot},
     style::{Align, Flag, GridAlign, State},
 };
 
 use crate::style::Style;
+#[cfg(feature = "async")]
+use crate::core::EventReceiver;
 use cty::uint8_t;
 
-/// Base LVGL object. C is the application context that we provide to the
-/// callbacks The lifetime of the object depends on the lifetime of its parent.
+/// Base LVGL object. C is the type of the application context that
+/// `Lvgl::run_tasks()` lends to the callbacks. The lifetime of the object
+/// depends on the lifetime of its parent.
 /// (in lvgl, deleting an object deletes all its children).
 /// We have not implemented lifetimes correctly at this point.
 pub struct Obj<C> {
     // pub so that the user can use lvgl_sys functions directly
     pub raw: &'static mut lv_obj_t,
-    // We want a stable pointer.
-    pub(crate) context: ptr::NonNull<Option<C>>, // Should be a refcell? Or a &mut? Lost patience trying to make things work.
+    _context: PhantomData<*mut C>,
 }
 
-unsafe impl<C> Send for Obj<C> {}
-
 impl<C> Obj<C> {
-    pub fn from_raw(raw: &'static mut lv_obj_t, context: ptr::NonNull<Option<C>>) -> Self {
-        Self { raw, context }
+    pub fn from_raw(raw: &'static mut lv_obj_t) -> Self {
+        Self {
+            raw,
+            _context: PhantomData,
+        }
     }
-}
 
-pub trait ObjExt<C: 'static>: Deref<Target = Obj<C>> + DerefMut + Sized {
-    fn context(&mut self) -> &mut Option<C> {
-        unsafe { self.context.as_mut() }
+    // For the lvgl functions that take a mutable object even though they
+    // don't modify it, e.g. many getters.
+    pub(crate) fn raw_mut_const(&self) -> *mut lv_obj_t {
+        &*self.raw as *const _ as *mut _
     }
+}
 
+pub trait ObjExt<C: 'static>: Deref<Target = Obj<C>> + DerefMut + Sized {
     fn apply(mut self, f: impl FnOnce(&mut Self)) -> Self {
         // We don't care about the return value. It's typically &mut self
         f(&mut self);
         self
     }
 
-    /// Register an event callback, for a specific event
+    /// Register an event callback, for a specific event.
+    /// The callback only runs when the event fires from `Lvgl::run_tasks()`,
+    /// and not while another callback runs: an event fired from a callback,
+    /// e.g. `ValueChanged` by a `set_value()`, is dropped.
     fn on_event(&mut self, event: Event, mut f: impl FnMut(&mut C) + 'static) -> &mut Self {
-        let mut context = self.context;
         add_event_cb(self.raw, Some(event), move |_e, _current_target, _child| {
-            let context = unsafe { context.as_mut().as_mut() };
-            let context = context.expect("screen.context() must be set");
-            f(context)
+            with_context(|context| f(context));
         });
         self
     }
 
     /// Register an event callback, receiving all events.
+    /// The callback only runs when the event fires from `Lvgl::run_tasks()`,
+    /// and not while another callback runs: an event fired from a callback,
+    /// e.g. `ValueChanged` by a `set_value()`, is dropped.
     fn on_any_event(&mut self, mut f: impl FnMut(&mut C, Event) + 'static) -> &mut Self {
-        let mut context = self.context;
         add_event_cb(self.raw, None, move |e, _current_target, _child| {
-            let context = unsafe { context.as_mut().as_mut() };
-            let context = context.expect("screen.context() must be set");
-            f(context, e)
+            with_context(|context| f(context, e));
         });
         self
     }
 
+    /// Deliver an event to an async task. The events are queued by the
+    /// returned receiver, and they are dropped once the receiver is dropped.
+    #[cfg(feature = "async")]
+    fn on_event_async(&mut self, event: Event) -> EventReceiver {
+        EventReceiver::new(self.raw, Some(event))
+    }
+
     fn align_to(
         &mut self,
         base: &impl ObjExt<C>,
@@ -163,6 +176,13 @@ pub trait ObjExt<C: 'static>: Deref<Target = Obj<C>> + DerefMut + Sized {
     fn add_style(&mut self, style: &mut Style, selector: u32) {
         unsafe { lvgl_sys::lv_obj_add_style(&mut *self.raw, &mut *style.raw, selector) }
     }
+
+    /// Render the object and its children, for thumbnails or to compare
+    /// them with the expected pixels. None if the object has no size or if
+    /// the memory is short.
+    fn snapshot(&self) -> Option<Snapshot> {
+        Snapshot::take(self.raw_mut_const())
+    }
 }
 
 impl<C: 'static, T: Deref<Target = Obj<C>> + DerefMut + Sized> ObjExt<C> for T {}
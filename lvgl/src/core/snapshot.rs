@@ -0,0 +1,95 @@
+use core::ptr::NonNull;
+#[cfg(lv_color_raw)]
+use embedded_graphics::image::ImageRaw;
+#[cfg(all(lv_color_raw, target_endian = "big"))]
+use embedded_graphics::pixelcolor::raw::BigEndian as NativeEndian;
+#[cfg(all(lv_color_raw, target_endian = "little"))]
+use embedded_graphics::pixelcolor::raw::LittleEndian as NativeEndian;
+use embedded_graphics::prelude::*;
+use lvgl_sys::lv_obj_t;
+
+use super::PixelColor;
+
+/// The pixels of an object, see `ObjExt::snapshot()`. They are freed when
+/// the snapshot is dropped.
+pub struct Snapshot {
+    raw: NonNull<lvgl_sys::lv_img_dsc_t>,
+}
+
+impl Snapshot {
+    pub(crate) fn take(obj: *mut lv_obj_t) -> Option<Self> {
+        let cf = lvgl_sys::LV_IMG_CF_TRUE_COLOR as lvgl_sys::lv_img_cf_t;
+        let raw = unsafe { lvgl_sys::lv_snapshot_take(obj, cf) };
+        NonNull::new(raw).map(|raw| Self { raw })
+    }
+
+    pub fn size(&self) -> Size {
+        let header = &self.raw().header;
+        Size::new(header.w(), header.h())
+    }
+
+    /// The pixels, row by row.
+    pub fn pixels(&self) -> &[PixelColor] {
+        let size = self.size();
+        // PixelColor has the memory layout of lv_color_t.
+        unsafe {
+            core::slice::from_raw_parts(
+                self.raw().data as *const PixelColor,
+                (size.width * size.height) as usize,
+            )
+        }
+    }
+
+    /// The pixels as an image, to draw them or to compare them. The image
+    /// borrows the pixels, which is only possible with a `LV_COLOR_DEPTH` of
+    /// 16, without `LV_COLOR_16_SWAP`. Use `pixels()` otherwise.
+    #[cfg(lv_color_raw)]
+    pub fn image(&self) -> ImageRaw<'_, PixelColor, NativeEndian> {
+        let raw = self.raw();
+        let data = unsafe { core::slice::from_raw_parts(raw.data, raw.data_size as usize) };
+        ImageRaw::new(data, raw.header.w())
+    }
+
+    fn raw(&self) -> &lvgl_sys::lv_img_dsc_t {
+        unsafe { self.raw.as_ref() }
+    }
+}
+
+impl Drop for Snapshot {
+    fn drop(&mut self) {
+        unsafe { lvgl_sys::lv_snapshot_free(self.raw.as_ptr()) };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::prelude::*;
+    #[cfg(lv_color_raw)]
+    use embedded_graphics::{image::Image, mock_display::MockDisplay};
+
+    use crate::core::{ObjExt, PixelColor};
+    use crate::support::test::with_screen;
+    use crate::widgets::Canvas;
+
+    #[test]
+    fn snapshot_canvas() {
+        with_screen(|screen| {
+            let mut canvas = Canvas::with_size(screen, 8, 4);
+            canvas.clear(PixelColor::RED).unwrap();
+
+            let snapshot = canvas.snapshot().unwrap();
+            assert_eq!(snapshot.size(), Size::new(8, 4));
+            assert!(snapshot.pixels().iter().all(|&c| c == PixelColor::RED));
+
+            #[cfg(lv_color_raw)]
+            {
+                let mut display = MockDisplay::new();
+                Image::new(&snapshot.image(), Point::zero())
+                    .draw(&mut display)
+                    .unwrap();
+                assert_eq!(display.affected_area().size, Size::new(8, 4));
+                assert_eq!(display.get_pixel(Point::new(7, 3)), Some(PixelColor::RED));
+            }
+        });
+    }
+}
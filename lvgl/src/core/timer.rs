@@ -0,0 +1,167 @@
+use alloc::boxed::Box;
+use core::ptr::{self, NonNull};
+
+/// Pass to `Timer::set_repeat_count()` to run a timer forever.
+pub const TIMER_REPEAT_INFINITE: i32 = -1;
+
+struct TimerState {
+    // Null once lvgl has deleted the timer, when its repeat count is exhausted.
+    raw: *mut lvgl_sys::lv_timer_t,
+    cb: Box<dyn FnMut()>,
+    // Set while the closure runs, the handle dropped meanwhile sets `dropped`
+    // and the state is freed once the closure returns.
+    running: bool,
+    dropped: bool,
+}
+
+/// A timer, running a closure periodically from `Lvgl::run_tasks()`.
+/// Dropping the handle deletes the timer and frees the closure, so keep it
+/// around as long as the timer should run. It can be dropped from its own
+/// callback, the closure is then freed once it returns.
+pub struct Timer {
+    // Owned by the handle, unless the handle is dropped while the closure runs.
+    state: NonNull<TimerState>,
+}
+
+impl Timer {
+    pub(crate) fn new(period: u32, cb: Box<dyn FnMut()>) -> Self {
+        let state = Box::into_raw(Box::new(TimerState {
+            raw: ptr::null_mut(),
+            cb,
+            running: false,
+            dropped: false,
+        }));
+        unsafe {
+            let raw = lvgl_sys::lv_timer_create(Some(timer_callback), period, state.cast());
+            (*state).raw = raw.as_mut().expect("OOM");
+            Self {
+                state: NonNull::new_unchecked(state),
+            }
+        }
+    }
+
+    fn raw(&mut self) -> Option<&mut lvgl_sys::lv_timer_t> {
+        // The state isn't borrowed as a whole, the closure may be running.
+        unsafe { (*self.state.as_ptr()).raw.as_mut() }
+    }
+
+    pub fn pause(&mut self) -> &mut Self {
+        if let Some(raw) = self.raw() {
+            unsafe { lvgl_sys::lv_timer_pause(raw) };
+        }
+        self
+    }
+
+    pub fn resume(&mut self) -> &mut Self {
+        if let Some(raw) = self.raw() {
+            unsafe { lvgl_sys::lv_timer_resume(raw) };
+        }
+        self
+    }
+
+    /// Set the period of the timer, in milliseconds.
+    pub fn set_period(&mut self, period: u32) -> &mut Self {
+        if let Some(raw) = self.raw() {
+            unsafe { lvgl_sys::lv_timer_set_period(raw, period) };
+        }
+        self
+    }
+
+    /// How many times the timer runs before being deleted. See `TIMER_REPEAT_INFINITE`.
+    pub fn set_repeat_count(&mut self, count: i32) -> &mut Self {
+        if let Some(raw) = self.raw() {
+            unsafe { lvgl_sys::lv_timer_set_repeat_count(raw, count) };
+        }
+        self
+    }
+
+    /// Restart the period of the timer from now.
+    pub fn reset(&mut self) -> &mut Self {
+        if let Some(raw) = self.raw() {
+            unsafe { lvgl_sys::lv_timer_reset(raw) };
+        }
+        self
+    }
+
+    /// Run the timer on the next call of `Lvgl::run_tasks()`.
+    pub fn ready(&mut self) -> &mut Self {
+        if let Some(raw) = self.raw() {
+            unsafe { lvgl_sys::lv_timer_ready(raw) };
+        }
+        self
+    }
+
+    /// Whether the timer was deleted because its repeat count is exhausted.
+    pub fn is_deleted(&self) -> bool {
+        unsafe { (*self.state.as_ptr()).raw.is_null() }
+    }
+
+    /// Delete the timer and free its closure. This is the same as dropping it.
+    pub fn delete(self) {}
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let state = self.state.as_ptr();
+        unsafe {
+            if (*state).running {
+                (*state).dropped = true;
+            } else {
+                free_state(state);
+            }
+        }
+    }
+}
+
+unsafe fn free_state(state: *mut TimerState) {
+    let state = Box::from_raw(state);
+    if let Some(raw) = state.raw.as_mut() {
+        // lvgl supports deleting a timer from its own callback.
+        lvgl_sys::lv_timer_del(raw);
+    }
+}
+
+unsafe extern "C" fn timer_callback(timer: *mut lvgl_sys::lv_timer_t) {
+    let timer = timer.as_mut().unwrap();
+    let state: *mut TimerState = timer.user_data.cast();
+    (*state).running = true;
+    ((*state).cb)();
+    (*state).running = false;
+    // lvgl deletes the timer right after this callback when the count is exhausted.
+    if timer.repeat_count == 0 {
+        (*state).raw = ptr::null_mut();
+    }
+    if (*state).dropped {
+        free_state(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    use crate::core::{Lvgl, Timer};
+    use crate::support::test::with_screen;
+
+    #[test]
+    fn drop_from_callback() {
+        with_screen(|_screen| {
+            let mut lvgl = Lvgl::new();
+            let runs = Rc::new(Cell::new(0));
+            let counter = runs.clone();
+            let mut timer = lvgl.create_timer(1000, move |timer: &mut Option<Timer>| {
+                counter.set(counter.get() + 1);
+                timer.take();
+            });
+            timer.ready();
+
+            let mut context = Some(timer);
+            lvgl.run_tasks(&mut context);
+            assert!(context.is_none());
+            assert_eq!(runs.get(), 1);
+            // The closure is freed, with the counter it captured.
+            assert_eq!(Rc::strong_count(&runs), 1);
+        });
+    }
+}
@@ -17,6 +17,8 @@
 #[macro_use]
 extern crate bitflags;
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 #[cfg(feature = "lvgl_alloc")]
 pub mod allocator;
@@ -29,9 +31,14 @@ pub mod widgets;
 pub mod style;
 pub(crate) use style::native_enum;
 
+mod support;
+
 pub mod prelude {
     pub use crate::core::ObjExt;
 }
 
 pub use lvgl_sys as sys;
 pub use cstr_core;
+
+#[cfg(feature = "include-image")]
+pub use lvgl_macros::include_image;
@@ -13,6 +13,9 @@ pub use state::*;
 mod flag;
 pub use flag::*;
 
+mod dir;
+pub use dir::*;
+
 
 // Adapted from https://stackoverflow.com/questions/28028854/how-do-i-match-enum-values-with-an-integer
 macro_rules! native_enum {
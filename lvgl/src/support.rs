@@ -0,0 +1,99 @@
+use alloc::vec::Vec;
+
+/// Pass `text` to a C function expecting a null terminated string, for the
+/// functions of lvgl that copy the text. The text stops at the first null byte.
+pub(crate) fn with_cstr<R>(text: &str, f: impl FnOnce(*const cty::c_char) -> R) -> R {
+    let text = text.split('\0').next().unwrap_or_default();
+    let mut buf = Vec::with_capacity(text.len() + 1);
+    buf.extend_from_slice(text.as_bytes());
+    buf.push(0);
+    f(buf.as_ptr() as *const cty::c_char)
+}
+
+/// Like `with_cstr()`, for a list of options separated by "\n", as expected
+/// by the dropdowns and the rollers.
+pub(crate) fn with_options<'a, R>(
+    options: impl IntoIterator<Item = &'a str>,
+    f: impl FnOnce(*const cty::c_char) -> R,
+) -> R {
+    let mut buf = Vec::new();
+    for (i, option) in options.into_iter().enumerate() {
+        if i > 0 {
+            buf.push(b'\n');
+        }
+        buf.extend(option.bytes().take_while(|&b| b != 0));
+    }
+    buf.push(0);
+    f(buf.as_ptr() as *const cty::c_char)
+}
+
+/// Let `read` write a null terminated string of at most `N` bytes, and copy
+/// it to `text`. A character cut by the end of the buffer is dropped.
+pub(crate) fn read_cstr<const N: usize>(
+    text: &mut heapless::String<N>,
+    read: impl FnOnce(*mut cty::c_char, u32),
+) {
+    let mut buf = [0u8; N];
+    if N > 0 {
+        read(buf.as_mut_ptr() as *mut cty::c_char, N as u32);
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(N);
+    let valid = match core::str::from_utf8(&buf[..len]) {
+        Ok(valid) => valid,
+        Err(e) => core::str::from_utf8(&buf[..e.valid_up_to()]).unwrap_or_default(),
+    };
+    text.clear();
+    // Can't fail, the text is at most N bytes.
+    let _ = text.push_str(valid);
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use core::{convert::Infallible, mem::MaybeUninit, ptr};
+    use embedded_graphics::prelude::*;
+    use std::sync::Mutex;
+
+    use crate::core::{Display, Lvgl, PixelColor, Screen};
+
+    // A display that drops the pixels, so that the tests can run headlessly.
+    pub(crate) struct Headless;
+
+    impl DrawTarget for Headless {
+        type Color = PixelColor;
+        type Error = Infallible;
+
+        fn draw_iter<I>(&mut self, _pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            Ok(())
+        }
+    }
+
+    impl OriginDimensions for Headless {
+        fn size(&self) -> Size {
+            Size::new(320, 240)
+        }
+    }
+
+    // lvgl isn't thread safe, and the tests run on several threads.
+    static LOCK: Mutex<()> = Mutex::new(());
+    // Displays can't be dropped, the tests share this one.
+    static mut DISPLAY: Option<Display<Headless>> = None;
+
+    /// Run `f` with a new screen, deleted afterwards. The tests calling this
+    /// function run one at a time.
+    pub(crate) fn with_screen(f: impl FnOnce(&mut Screen<()>)) {
+        let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let display = unsafe { &mut *ptr::addr_of_mut!(DISPLAY) };
+        let display = display.get_or_insert_with(|| {
+            let lvgl = Lvgl::new();
+            let buffer = Box::leak(vec![MaybeUninit::uninit(); 320 * 10].into_boxed_slice());
+            Display::new(&lvgl, Headless, buffer)
+        });
+
+        let mut screen = Screen::new(display);
+        f(&mut screen);
+        unsafe { lvgl_sys::lv_obj_del(&mut *screen.raw) };
+    }
+}
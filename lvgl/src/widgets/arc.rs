@@ -1,61 +1,120 @@
-//use crate::widgets::Arc;
-
-/*
-impl Arc {
-    // /// Set the start angle, for the given arc part.
-    // /// 0 degrees for the right, 90 degrees for the bottom, etc.
-    // pub fn set_start_angle(&mut self, angle: u16, part: ArcPart) -> LvResult<()> {
-    //     match part {
-    //         ArcPart::Background => unsafe {
-    //             lvgl_sys::lv_arc_set_bg_start_angle(self.core.raw()?.as_mut(), angle)
-    //         },
-    //         ArcPart::Indicator => unsafe {
-    //             lvgl_sys::lv_arc_set_start_angle(self.core.raw()?.as_mut(), angle)
-    //         },
-    //     }
-    //     Ok(())
-    // }
-    //
-    // /// Set the end angle, for the given arc part.
-    // /// 0 degrees for the right, 90 degrees for the bottom, etc.
-    // pub fn set_end_angle(&self, angle: u16, part: ArcPart) -> LvResult<()> {
-    //     match part {
-    //         ArcPart::Background => unsafe {
-    //             lvgl_sys::lv_arc_set_bg_end_angle(self.core.raw()?.as_mut(), angle)
-    //         },
-    //         ArcPart::Indicator => unsafe {
-    //             lvgl_sys::lv_arc_set_end_angle(self.core.raw()?.as_mut(), angle)
-    //         },
-    //     }
-    //     Ok(())
-    // }
-    //
-    // /// Rotate the arc, `angle` degrees clockwise.
-    // pub fn set_rotation(&mut self, angle: u16) -> LvResult<()> {
-    //     unsafe {
-    //         lvgl_sys::lv_arc_set_rotation(self.core.raw()?.as_mut(), angle);
-    //     }
-    //     Ok(())
-    // }
-}
-*/
+use core::convert::TryInto;
+
+use crate::widgets::Arc;
 
-/*
 /// The different parts, of an arc object.
 #[derive(Debug, Copy, Clone, PartialEq)]
-#[repr(u8)]
 pub enum ArcPart {
     /// The background of the arc.
-    Background = lvgl_sys::LV_ARC_PART_BG as u8,
+    Background,
     /// The indicator of the arc.
     /// This is what moves/changes, depending on the arc's value.
-    Indicator = lvgl_sys::LV_ARC_PART_INDIC as u8,
+    Indicator,
 }
 
-impl From<ArcPart> for u8 {
-    fn from(part: ArcPart) -> Self {
-        part as u8
+crate::native_enum! {
+    lvgl_sys::lv_arc_mode_t,
+    /// Where the indicator starts from.
+    pub enum ArcMode {
+        /// From the start angle, clockwise
+        Normal = lvgl_sys::LV_ARC_MODE_NORMAL,
+        /// From the middle of the background
+        Symmetrical = lvgl_sys::LV_ARC_MODE_SYMMETRICAL,
+        /// From the end angle, counter-clockwise
+        Reverse = lvgl_sys::LV_ARC_MODE_REVERSE,
     }
 }
 
-*/
+impl<C: 'static> Arc<C> {
+    /// Set the start and end angles, for the given arc part.
+    /// 0 degrees for the right, 90 degrees for the bottom, etc.
+    pub fn set_part_angles(&mut self, part: ArcPart, start: u16, end: u16) -> &mut Self {
+        match part {
+            ArcPart::Background => unsafe {
+                lvgl_sys::lv_arc_set_bg_angles(&mut *self.raw, start, end)
+            },
+            ArcPart::Indicator => unsafe {
+                lvgl_sys::lv_arc_set_angles(&mut *self.raw, start, end)
+            },
+        }
+        self
+    }
+
+    /// The start and end angles, for the given arc part.
+    pub fn get_part_angles(&self, part: ArcPart) -> (u16, u16) {
+        unsafe {
+            match part {
+                ArcPart::Background => (
+                    lvgl_sys::lv_arc_get_bg_angle_start(self.raw_mut_const()),
+                    lvgl_sys::lv_arc_get_bg_angle_end(self.raw_mut_const()),
+                ),
+                ArcPart::Indicator => (
+                    lvgl_sys::lv_arc_get_angle_start(self.raw_mut_const()),
+                    lvgl_sys::lv_arc_get_angle_end(self.raw_mut_const()),
+                ),
+            }
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: ArcMode) -> &mut Self {
+        unsafe { lvgl_sys::lv_arc_set_mode(&mut *self.raw, mode.into()) };
+        self
+    }
+
+    pub fn get_mode(&self) -> ArcMode {
+        let mode = unsafe { lvgl_sys::lv_arc_get_mode(&*self.raw) };
+        mode.try_into().expect("unknown mode")
+    }
+
+    pub fn get_value(&self) -> i16 {
+        unsafe { lvgl_sys::lv_arc_get_value(&*self.raw) }
+    }
+
+    pub fn get_min_value(&self) -> i16 {
+        unsafe { lvgl_sys::lv_arc_get_min_value(&*self.raw) }
+    }
+
+    pub fn get_max_value(&self) -> i16 {
+        unsafe { lvgl_sys::lv_arc_get_max_value(&*self.raw) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::support::test::with_screen;
+    use crate::widgets::{Arc, ArcMode, ArcPart};
+
+    #[test]
+    fn set_part_angles() {
+        with_screen(|screen| {
+            let mut arc = Arc::new(screen);
+            arc.set_part_angles(ArcPart::Background, 135, 45)
+                .set_part_angles(ArcPart::Indicator, 135, 270);
+
+            assert_eq!(arc.get_part_angles(ArcPart::Background), (135, 45));
+            assert_eq!(arc.get_part_angles(ArcPart::Indicator), (135, 270));
+        });
+    }
+
+    #[test]
+    fn value_is_clamped_to_range() {
+        with_screen(|screen| {
+            let mut arc = Arc::new(screen);
+            arc.set_range(-10, 10).set_value(50);
+
+            assert_eq!(arc.get_min_value(), -10);
+            assert_eq!(arc.get_max_value(), 10);
+            assert_eq!(arc.get_value(), 10);
+        });
+    }
+
+    #[test]
+    fn set_mode() {
+        with_screen(|screen| {
+            let mut arc = Arc::new(screen);
+            arc.set_mode(ArcMode::Symmetrical);
+
+            assert!(matches!(arc.get_mode(), ArcMode::Symmetrical));
+        });
+    }
+}
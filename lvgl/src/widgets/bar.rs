@@ -1,44 +1,98 @@
-/*
-use crate::support::Animation;
+use core::convert::TryInto;
+
 use crate::widgets::Bar;
-use crate::{LvResult, NativeObject};
 
-impl Bar {
-    /// Set minimum and the maximum values of the bar
-    pub fn set_range(&mut self, min: i16, max: i16) -> LvResult<()> {
-        unsafe {
-            lvgl_sys::lv_bar_set_range(self.core.raw()?.as_mut(), min, max);
-        }
-        Ok(())
+crate::native_enum! {
+    lvgl_sys::lv_bar_mode_t,
+    pub enum BarMode {
+        /// The indicator goes from the minimum value to the value
+        Normal = lvgl_sys::LV_BAR_MODE_NORMAL,
+        /// The indicator goes from zero to the value
+        Symmetrical = lvgl_sys::LV_BAR_MODE_SYMMETRICAL,
+        /// The indicator goes from the start value to the value
+        Range = lvgl_sys::LV_BAR_MODE_RANGE,
     }
+}
 
-    /// Set a new value on the bar
-    pub fn set_value(&mut self, value: i16, anim: Animation) -> LvResult<()> {
-        unsafe {
-            lvgl_sys::lv_bar_set_value(self.core.raw()?.as_mut(), value, anim.into());
-        }
-        Ok(())
+impl<C: 'static> Bar<C> {
+    pub fn set_mode(&mut self, mode: BarMode) -> &mut Self {
+        unsafe { lvgl_sys::lv_bar_set_mode(&mut *self.raw, mode.into()) };
+        self
     }
-}
-*/
-
-/*
-/// The different parts, of a bar object.
-pub enum BarPart {
-    /// The background of the bar.
-    Background,
-    /// The indicator of the bar.
-    /// This is what moves/changes, depending on the bar's value.
-    Indicator,
-}
 
-impl From<BarPart> for u8 {
-    fn from(component: BarPart) -> Self {
-        match component {
-            BarPart::Background => lvgl_sys::LV_BAR_PART_BG as u8,
-            BarPart::Indicator => lvgl_sys::LV_BAR_PART_INDIC as u8,
-        }
+    pub fn get_mode(&self) -> BarMode {
+        let mode = unsafe { lvgl_sys::lv_bar_get_mode(self.raw_mut_const()) };
+        mode.try_into().expect("unknown mode")
+    }
+
+    /// The value of the bar. While animated, this is the target value.
+    pub fn get_value(&self) -> i32 {
+        unsafe { lvgl_sys::lv_bar_get_value(&*self.raw) }
+    }
+
+    /// The start value of the bar, only used in `BarMode::Range`.
+    pub fn get_start_value(&self) -> i32 {
+        unsafe { lvgl_sys::lv_bar_get_start_value(&*self.raw) }
+    }
+
+    pub fn get_min_value(&self) -> i32 {
+        unsafe { lvgl_sys::lv_bar_get_min_value(&*self.raw) }
+    }
+
+    pub fn get_max_value(&self) -> i32 {
+        unsafe { lvgl_sys::lv_bar_get_max_value(&*self.raw) }
     }
 }
 
-*/
+#[cfg(test)]
+mod test {
+    use crate::style::Animation;
+    use crate::support::test::with_screen;
+    use crate::widgets::{Bar, BarMode};
+
+    #[test]
+    fn set_range_and_value() {
+        with_screen(|screen| {
+            let mut bar = Bar::new(screen);
+            bar.set_range(-50, 50).set_value(20, Animation::Off);
+
+            assert_eq!(bar.get_min_value(), -50);
+            assert_eq!(bar.get_max_value(), 50);
+            assert_eq!(bar.get_value(), 20);
+        });
+    }
+
+    #[test]
+    fn animated_value_reports_the_target() {
+        with_screen(|screen| {
+            let mut bar = Bar::new(screen);
+            bar.set_value(80, Animation::On);
+
+            assert_eq!(bar.get_value(), 80);
+        });
+    }
+
+    #[test]
+    fn value_is_clamped_to_range() {
+        with_screen(|screen| {
+            let mut bar = Bar::new(screen);
+            bar.set_range(0, 10).set_value(20, Animation::Off);
+
+            assert_eq!(bar.get_value(), 10);
+        });
+    }
+
+    #[test]
+    fn start_value_in_range_mode() {
+        with_screen(|screen| {
+            let mut bar = Bar::new(screen);
+            bar.set_mode(BarMode::Range)
+                .set_value(70, Animation::Off)
+                .set_start_value(30, Animation::Off);
+
+            assert!(matches!(bar.get_mode(), BarMode::Range));
+            assert_eq!(bar.get_start_value(), 30);
+            assert_eq!(bar.get_value(), 70);
+        });
+    }
+}
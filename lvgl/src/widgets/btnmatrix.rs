@@ -0,0 +1,226 @@
+use alloc::vec::Vec;
+
+use crate::core::event_slot;
+use crate::widgets::BtnMatrix;
+
+bitflags! {
+    pub struct BtnMatrixCtrl: lvgl_sys::lv_btnmatrix_ctrl_t {
+        const HIDDEN = lvgl_sys::LV_BTNMATRIX_CTRL_HIDDEN;
+        /// Don't repeat the button when it's long pressed
+        const NO_REPEAT = lvgl_sys::LV_BTNMATRIX_CTRL_NO_REPEAT;
+        const DISABLED = lvgl_sys::LV_BTNMATRIX_CTRL_DISABLED;
+        /// The button can be toggled
+        const CHECKABLE = lvgl_sys::LV_BTNMATRIX_CTRL_CHECKABLE;
+        const CHECKED = lvgl_sys::LV_BTNMATRIX_CTRL_CHECKED;
+        /// Send `Event::ValueChanged` on click instead of press
+        const CLICK_TRIG = lvgl_sys::LV_BTNMATRIX_CTRL_CLICK_TRIG;
+        /// Enable the recoloring of the text, with `#ff0000 red#`
+        const RECOLOR = lvgl_sys::LV_BTNMATRIX_CTRL_RECOLOR;
+        const CUSTOM_1 = lvgl_sys::LV_BTNMATRIX_CTRL_CUSTOM_1;
+        const CUSTOM_2 = lvgl_sys::LV_BTNMATRIX_CTRL_CUSTOM_2;
+    }
+}
+
+// The lowest bits of the controls hold the width of the button.
+const WIDTH_MASK: lvgl_sys::lv_btnmatrix_ctrl_t =
+    lvgl_sys::_LV_BTNMATRIX_WIDTH as lvgl_sys::lv_btnmatrix_ctrl_t;
+
+/// The buttons of a button matrix or of a keyboard, given as rows of texts.
+/// ```ignore
+/// let mut map = BtnMatrixMap::new();
+/// map.add_row(&["1", "2", "3"])
+///     .add_row(&["Cancel", "OK"])
+///     .set_width(3, 2)
+///     .set_ctrl(4, BtnMatrixCtrl::CHECKABLE);
+/// btnm.set_map(map);
+/// ```
+#[derive(Default)]
+pub struct BtnMatrixMap {
+    // The texts of the buttons in the format of lvgl: "\n" between the rows,
+    // and "" at the end, each of them null terminated.
+    texts: Vec<u8>,
+    offsets: Vec<usize>,
+    // An entry per button, with the width in the lowest bits.
+    ctrl: Vec<lvgl_sys::lv_btnmatrix_ctrl_t>,
+}
+
+impl BtnMatrixMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a row of buttons. The buttons are numbered from the first row,
+    /// left to right.
+    pub fn add_row(&mut self, texts: &[&str]) -> &mut Self {
+        if !self.offsets.is_empty() {
+            self.push_text("\n");
+        }
+        for text in texts {
+            self.push_text(text);
+            self.ctrl.push(0);
+        }
+        self
+    }
+
+    /// Set the controls of a button. Panics if the button doesn't exist.
+    pub fn set_ctrl(&mut self, btn_id: usize, ctrl: BtnMatrixCtrl) -> &mut Self {
+        self.ctrl[btn_id] = (self.ctrl[btn_id] & WIDTH_MASK) | ctrl.bits();
+        self
+    }
+
+    /// Set the width of a button, relative to the other buttons of its row,
+    /// from 1 to 7. Panics if the button doesn't exist.
+    pub fn set_width(&mut self, btn_id: usize, width: u8) -> &mut Self {
+        let width = lvgl_sys::lv_btnmatrix_ctrl_t::from(width).clamp(1, WIDTH_MASK);
+        self.ctrl[btn_id] = (self.ctrl[btn_id] & !WIDTH_MASK) | width;
+        self
+    }
+
+    fn push_text(&mut self, text: &str) {
+        self.offsets.push(self.texts.len());
+        self.texts.extend(text.bytes().take_while(|&b| b != 0));
+        self.texts.push(0);
+    }
+
+    pub(crate) fn into_raw(mut self) -> RawBtnMatrixMap {
+        self.push_text("");
+        // The texts don't move anymore, we can point to them.
+        let map = self
+            .offsets
+            .iter()
+            .map(|&offset| self.texts[offset..].as_ptr() as *const cty::c_char)
+            .collect();
+        RawBtnMatrixMap {
+            _texts: self.texts,
+            map,
+            ctrl: self.ctrl,
+        }
+    }
+}
+
+// The arrays that lvgl expects. The map points to the texts.
+pub(crate) struct RawBtnMatrixMap {
+    _texts: Vec<u8>,
+    pub(crate) map: Vec<*const cty::c_char>,
+    pub(crate) ctrl: Vec<lvgl_sys::lv_btnmatrix_ctrl_t>,
+}
+
+impl<C: 'static> BtnMatrix<C> {
+    /// Replace the buttons. The map is kept until it's replaced or the
+    /// matrix is deleted.
+    pub fn set_map(&mut self, map: BtnMatrixMap) -> &mut Self {
+        let mut map = map.into_raw();
+        unsafe {
+            lvgl_sys::lv_btnmatrix_set_map(&mut *self.raw, map.map.as_mut_ptr());
+            lvgl_sys::lv_btnmatrix_set_ctrl_map(&mut *self.raw, map.ctrl.as_ptr());
+        }
+        self.keep_map(map);
+        self
+    }
+
+    pub fn set_btn_ctrl(&mut self, btn_id: u16, ctrl: BtnMatrixCtrl) -> &mut Self {
+        unsafe { lvgl_sys::lv_btnmatrix_set_btn_ctrl(&mut *self.raw, btn_id, ctrl.bits()) };
+        self
+    }
+
+    pub fn clear_btn_ctrl(&mut self, btn_id: u16, ctrl: BtnMatrixCtrl) -> &mut Self {
+        unsafe { lvgl_sys::lv_btnmatrix_clear_btn_ctrl(&mut *self.raw, btn_id, ctrl.bits()) };
+        self
+    }
+
+    pub fn set_btn_ctrl_all(&mut self, ctrl: BtnMatrixCtrl) -> &mut Self {
+        unsafe { lvgl_sys::lv_btnmatrix_set_btn_ctrl_all(&mut *self.raw, ctrl.bits()) };
+        self
+    }
+
+    pub fn clear_btn_ctrl_all(&mut self, ctrl: BtnMatrixCtrl) -> &mut Self {
+        unsafe { lvgl_sys::lv_btnmatrix_clear_btn_ctrl_all(&mut *self.raw, ctrl.bits()) };
+        self
+    }
+
+    /// Whether the button has all the flags of `ctrl`.
+    pub fn has_btn_ctrl(&self, btn_id: u16, ctrl: BtnMatrixCtrl) -> bool {
+        unsafe { lvgl_sys::lv_btnmatrix_has_btn_ctrl(self.raw_mut_const(), btn_id, ctrl.bits()) }
+    }
+
+    /// The last pressed or released button, if any.
+    pub fn get_selected_btn(&self) -> Option<u16> {
+        match unsafe { lvgl_sys::lv_btnmatrix_get_selected_btn(&*self.raw) } {
+            btn if u32::from(btn) == lvgl_sys::LV_BTNMATRIX_BTN_NONE => None,
+            btn => Some(btn),
+        }
+    }
+
+    /// The text of the last pressed or released button, if any.
+    pub fn selected_btn_text(&self) -> Option<&str> {
+        let btn = self.get_selected_btn()?;
+        unsafe {
+            let text = lvgl_sys::lv_btnmatrix_get_btn_text(&*self.raw, btn);
+            text.as_ref()
+                .map(|text| cstr_core::CStr::from_ptr(text).to_str().unwrap_or_default())
+        }
+    }
+
+    // The map is kept in a single slot, freed with the matrix. lvgl doesn't use
+    // the previous map anymore once it's replaced.
+    fn keep_map(&mut self, map: RawBtnMatrixMap) {
+        *event_slot(&mut *self.raw, || None) = Some(map);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cstr_core::CStr;
+
+    use super::RawBtnMatrixMap;
+    use crate::core::event_slot;
+    use crate::support::test::with_screen;
+    use crate::widgets::{BtnMatrix, BtnMatrixCtrl, BtnMatrixMap};
+
+    #[test]
+    fn map_in_lvgl_format() {
+        let mut map = BtnMatrixMap::new();
+        map.add_row(&["1", "2"]).add_row(&["OK"]);
+        let map = map.into_raw();
+
+        let texts: Vec<_> = map
+            .map
+            .iter()
+            .map(|&text| unsafe { CStr::from_ptr(text) }.to_str().unwrap())
+            .collect();
+        assert_eq!(texts, ["1", "2", "\n", "OK", ""]);
+        assert_eq!(map.ctrl.len(), 3);
+    }
+
+    #[test]
+    fn ctrl_keeps_width() {
+        let mut map = BtnMatrixMap::new();
+        map.add_row(&["1", "2"])
+            .set_width(1, 2)
+            .set_ctrl(1, BtnMatrixCtrl::CHECKABLE);
+        let map = map.into_raw();
+
+        assert_eq!(map.ctrl[0], 0);
+        assert_eq!(map.ctrl[1], 2 | BtnMatrixCtrl::CHECKABLE.bits());
+    }
+
+    #[test]
+    fn set_map_replaces_the_previous_one() {
+        with_screen(|screen| {
+            let mut btnm = BtnMatrix::new(screen);
+            let mut slots = Vec::new();
+            for text in ["A", "B", "C"] {
+                let mut map = BtnMatrixMap::new();
+                map.add_row(&[text]);
+                btnm.set_map(map);
+                let slot = event_slot(&mut *btnm.raw, || None::<RawBtnMatrixMap>);
+                slots.push(slot as *mut _);
+            }
+
+            let text =
+                unsafe { CStr::from_ptr(lvgl_sys::lv_btnmatrix_get_btn_text(&*btnm.raw, 0)) };
+            assert_eq!(text.to_str(), Ok("C"));
+            assert!(slots.iter().all(|&slot| slot == slots[0]));
+        });
+    }
+}
@@ -0,0 +1,393 @@
+use alloc::{vec, vec::Vec};
+use core::convert::Infallible;
+use embedded_graphics::prelude::*;
+use lvgl_sys::{lv_color_t, lv_coord_t, lv_opa_t};
+
+use crate::core::{keep_alive, lv_color, ObjExt, PixelColor};
+use crate::support::with_cstr;
+use crate::widgets::{Canvas, ImageDescriptor};
+
+macro_rules! draw_dsc {
+    ($(#[$meta:meta])* $name:ident, $raw:ident, $init:ident) => {
+        $(#[$meta])*
+        #[derive(Clone)]
+        pub struct $name {
+            // pub so that the user can set the other fields
+            pub raw: lvgl_sys::$raw,
+        }
+
+        impl $name {
+            pub fn new() -> Self {
+                let mut raw = lvgl_sys::$raw::default();
+                unsafe { lvgl_sys::$init(&mut raw) };
+                Self { raw }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+draw_dsc!(
+    /// How `Canvas::draw_rect()` draws a rectangle.
+    DrawRectDsc,
+    lv_draw_rect_dsc_t,
+    lv_draw_rect_dsc_init
+);
+draw_dsc!(
+    /// How `Canvas::draw_line()` draws lines.
+    DrawLineDsc,
+    lv_draw_line_dsc_t,
+    lv_draw_line_dsc_init
+);
+draw_dsc!(
+    /// How `Canvas::draw_arc()` draws an arc.
+    DrawArcDsc,
+    lv_draw_arc_dsc_t,
+    lv_draw_arc_dsc_init
+);
+draw_dsc!(
+    /// How `Canvas::draw_text()` draws a text, with the default font.
+    DrawLabelDsc,
+    lv_draw_label_dsc_t,
+    lv_draw_label_dsc_init
+);
+draw_dsc!(
+    /// How `Canvas::draw_img()` draws an image.
+    DrawImgDsc,
+    lv_draw_img_dsc_t,
+    lv_draw_img_dsc_init
+);
+
+impl DrawRectDsc {
+    pub fn set_radius(&mut self, radius: lv_coord_t) -> &mut Self {
+        self.raw.radius = radius;
+        self
+    }
+
+    pub fn set_bg_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.bg_color = lv_color(color);
+        self
+    }
+
+    pub fn set_bg_opa(&mut self, opa: lv_opa_t) -> &mut Self {
+        self.raw.bg_opa = opa;
+        self
+    }
+
+    pub fn set_border_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.border_color = lv_color(color);
+        self
+    }
+
+    pub fn set_border_width(&mut self, width: lv_coord_t) -> &mut Self {
+        self.raw.border_width = width;
+        self
+    }
+
+    pub fn set_border_opa(&mut self, opa: lv_opa_t) -> &mut Self {
+        self.raw.border_opa = opa;
+        self
+    }
+}
+
+impl DrawLineDsc {
+    pub fn set_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.color = lv_color(color);
+        self
+    }
+
+    pub fn set_width(&mut self, width: lv_coord_t) -> &mut Self {
+        self.raw.width = width;
+        self
+    }
+
+    pub fn set_opa(&mut self, opa: lv_opa_t) -> &mut Self {
+        self.raw.opa = opa;
+        self
+    }
+
+    /// Round both ends of the lines.
+    pub fn set_rounded(&mut self, rounded: bool) -> &mut Self {
+        self.raw.set_round_start(rounded.into());
+        self.raw.set_round_end(rounded.into());
+        self
+    }
+}
+
+impl DrawArcDsc {
+    pub fn set_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.color = lv_color(color);
+        self
+    }
+
+    pub fn set_width(&mut self, width: lv_coord_t) -> &mut Self {
+        self.raw.width = width;
+        self
+    }
+
+    pub fn set_opa(&mut self, opa: lv_opa_t) -> &mut Self {
+        self.raw.opa = opa;
+        self
+    }
+
+    /// Round both ends of the arc.
+    pub fn set_rounded(&mut self, rounded: bool) -> &mut Self {
+        self.raw.set_rounded(rounded.into());
+        self
+    }
+}
+
+impl DrawLabelDsc {
+    pub fn set_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.color = lv_color(color);
+        self
+    }
+
+    pub fn set_opa(&mut self, opa: lv_opa_t) -> &mut Self {
+        self.raw.opa = opa;
+        self
+    }
+
+    pub fn set_letter_space(&mut self, space: lv_coord_t) -> &mut Self {
+        self.raw.letter_space = space;
+        self
+    }
+
+    pub fn set_line_space(&mut self, space: lv_coord_t) -> &mut Self {
+        self.raw.line_space = space;
+        self
+    }
+}
+
+impl DrawImgDsc {
+    /// The rotation, in 0.1 degrees.
+    pub fn set_angle(&mut self, angle: u16) -> &mut Self {
+        self.raw.angle = angle;
+        self
+    }
+
+    /// The zoom factor, 256 being the original size.
+    pub fn set_zoom(&mut self, zoom: u16) -> &mut Self {
+        self.raw.zoom = zoom;
+        self
+    }
+
+    pub fn set_opa(&mut self, opa: lv_opa_t) -> &mut Self {
+        self.raw.opa = opa;
+        self
+    }
+
+    /// Mix the pixels with `color`.
+    pub fn set_recolor(&mut self, color: PixelColor, opa: lv_opa_t) -> &mut Self {
+        self.raw.recolor = lv_color(color);
+        self.raw.recolor_opa = opa;
+        self
+    }
+}
+
+impl<C: 'static> Canvas<C> {
+    /// Create a canvas with a buffer of `width` x `height` pixels, freed with
+    /// the canvas.
+    pub fn with_size(parent: &mut impl ObjExt<C>, width: lv_coord_t, height: lv_coord_t) -> Self {
+        let mut canvas = Self::new(parent);
+        let len = width.max(0) as usize * height.max(0) as usize;
+        let mut buf = vec![lv_color_t::default(); len].into_boxed_slice();
+        unsafe {
+            lvgl_sys::lv_canvas_set_buffer(
+                &mut *canvas.raw,
+                buf.as_mut_ptr() as *mut cty::c_void,
+                width,
+                height,
+                lvgl_sys::LV_IMG_CF_TRUE_COLOR as lvgl_sys::lv_img_cf_t,
+            )
+        };
+        keep_alive(canvas.raw, buf);
+        canvas
+    }
+
+    pub fn draw_rect(
+        &mut self,
+        x: lv_coord_t,
+        y: lv_coord_t,
+        w: lv_coord_t,
+        h: lv_coord_t,
+        dsc: &DrawRectDsc,
+    ) -> &mut Self {
+        unsafe { lvgl_sys::lv_canvas_draw_rect(&mut *self.raw, x, y, w, h, &dsc.raw) };
+        self
+    }
+
+    /// Draw lines connecting the points.
+    pub fn draw_line(&mut self, points: &[Point], dsc: &DrawLineDsc) -> &mut Self {
+        let points: Vec<_> = points
+            .iter()
+            .map(|p| lvgl_sys::lv_point_t {
+                x: p.x as lv_coord_t,
+                y: p.y as lv_coord_t,
+            })
+            .collect();
+        unsafe {
+            lvgl_sys::lv_canvas_draw_line(
+                &mut *self.raw,
+                points.as_ptr(),
+                points.len() as u32,
+                &dsc.raw,
+            )
+        };
+        self
+    }
+
+    /// Draw an arc centered on `(x, y)`, clockwise from `start_angle` to
+    /// `end_angle` in degrees, 0 being 3 o'clock.
+    pub fn draw_arc(
+        &mut self,
+        x: lv_coord_t,
+        y: lv_coord_t,
+        r: lv_coord_t,
+        start_angle: i32,
+        end_angle: i32,
+        dsc: &DrawArcDsc,
+    ) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_canvas_draw_arc(&mut *self.raw, x, y, r, start_angle, end_angle, &dsc.raw)
+        };
+        self
+    }
+
+    /// Draw a text, wrapped at `max_w` pixels.
+    pub fn draw_text(
+        &mut self,
+        x: lv_coord_t,
+        y: lv_coord_t,
+        max_w: lv_coord_t,
+        dsc: &mut DrawLabelDsc,
+        text: &str,
+    ) -> &mut Self {
+        with_cstr(text, |text| unsafe {
+            lvgl_sys::lv_canvas_draw_text(&mut *self.raw, x, y, max_w, &mut dsc.raw, text)
+        });
+        self
+    }
+
+    pub fn draw_img(
+        &mut self,
+        x: lv_coord_t,
+        y: lv_coord_t,
+        src: &ImageDescriptor,
+        dsc: &DrawImgDsc,
+    ) -> &mut Self {
+        unsafe { lvgl_sys::lv_canvas_draw_img(&mut *self.raw, x, y, src.as_src(), &dsc.raw) };
+        self
+    }
+
+    fn img(&self) -> &lvgl_sys::lv_img_dsc_t {
+        unsafe { &*lvgl_sys::lv_canvas_get_img(self.raw_mut_const()) }
+    }
+}
+
+/// Draw with embedded-graphics. The pixels are set in the buffer, and the
+/// canvas is redrawn by lvgl afterwards.
+impl<C: 'static> DrawTarget for Canvas<C> {
+    type Color = PixelColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let img = *self.img();
+        let (width, height) = (img.header.w() as i32, img.header.h() as i32);
+        let true_color = img.header.cf() == lvgl_sys::LV_IMG_CF_TRUE_COLOR;
+        for Pixel(Point { x, y }, color) in pixels {
+            if x < 0 || y < 0 || x >= width || y >= height {
+                continue;
+            }
+            if true_color {
+                unsafe {
+                    let buf = img.data as *mut lv_color_t;
+                    *buf.add((y * width + x) as usize) = lv_color(color);
+                }
+            } else {
+                unsafe {
+                    lvgl_sys::lv_canvas_set_px_color(
+                        &mut *self.raw,
+                        x as lv_coord_t,
+                        y as lv_coord_t,
+                        lv_color(color),
+                    )
+                };
+            }
+        }
+        unsafe { lvgl_sys::lv_obj_invalidate(&*self.raw) };
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        unsafe {
+            lvgl_sys::lv_canvas_fill_bg(
+                &mut *self.raw,
+                lv_color(color),
+                lvgl_sys::LV_OPA_COVER as lv_opa_t,
+            )
+        };
+        Ok(())
+    }
+}
+
+impl<C: 'static> OriginDimensions for Canvas<C> {
+    fn size(&self) -> Size {
+        let header = &self.img().header;
+        Size::new(header.w(), header.h())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::prelude::*;
+    use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+
+    use crate::core::{lv_color, PixelColor};
+    use crate::support::test::with_screen;
+    use crate::widgets::{Canvas, DrawRectDsc};
+
+    fn px(canvas: &mut Canvas<()>, x: i16, y: i16) -> u16 {
+        unsafe { lvgl_sys::lv_canvas_get_px(&mut *canvas.raw, x, y).full }
+    }
+
+    #[test]
+    fn draw_with_embedded_graphics() {
+        with_screen(|screen| {
+            let mut canvas = Canvas::with_size(screen, 20, 10);
+            assert_eq!(canvas.size(), Size::new(20, 10));
+
+            canvas.clear(PixelColor::BLACK).unwrap();
+            Rectangle::new(Point::new(2, 2), Size::new(4, 4))
+                .into_styled(PrimitiveStyle::with_fill(PixelColor::RED))
+                .draw(&mut canvas)
+                .unwrap();
+
+            let red = unsafe { lv_color(PixelColor::RED).full };
+            let black = unsafe { lv_color(PixelColor::BLACK).full };
+            assert_eq!(px(&mut canvas, 3, 3), red);
+            assert_eq!(px(&mut canvas, 7, 3), black);
+        });
+    }
+
+    #[test]
+    fn draw_rect() {
+        with_screen(|screen| {
+            let mut canvas = Canvas::with_size(screen, 20, 10);
+            let mut dsc = DrawRectDsc::new();
+            dsc.set_bg_color(PixelColor::BLUE);
+            canvas.draw_rect(0, 0, 5, 5, &dsc);
+
+            let blue = unsafe { lv_color(PixelColor::BLUE).full };
+            assert_eq!(px(&mut canvas, 2, 2), blue);
+        });
+    }
+}
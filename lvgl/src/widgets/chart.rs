@@ -0,0 +1,400 @@
+use alloc::{boxed::Box, rc::Rc, vec::Vec};
+use core::{
+    cell::{Cell, RefCell},
+    ptr::{self, NonNull},
+};
+use embedded_graphics::prelude::Point;
+use lvgl_sys::{lv_coord_t, lv_obj_t};
+
+use crate::core::{event_slot, lv_color, PixelColor};
+use crate::style::Dir;
+use crate::widgets::Chart;
+
+crate::native_enum! {
+    lvgl_sys::lv_chart_type_t,
+    /// How the points of the series are drawn.
+    pub enum ChartType {
+        /// Don't draw the series
+        None = lvgl_sys::LV_CHART_TYPE_NONE,
+        /// Connect the points with lines
+        Line = lvgl_sys::LV_CHART_TYPE_LINE,
+        Bar = lvgl_sys::LV_CHART_TYPE_BAR,
+        /// Like `Line`, but with X values as well
+        Scatter = lvgl_sys::LV_CHART_TYPE_SCATTER,
+    }
+}
+
+crate::native_enum! {
+    lvgl_sys::lv_chart_axis_t,
+    pub enum ChartAxis {
+        PrimaryY = lvgl_sys::LV_CHART_AXIS_PRIMARY_Y,
+        SecondaryY = lvgl_sys::LV_CHART_AXIS_SECONDARY_Y,
+        PrimaryX = lvgl_sys::LV_CHART_AXIS_PRIMARY_X,
+        SecondaryX = lvgl_sys::LV_CHART_AXIS_SECONDARY_X,
+    }
+}
+
+crate::native_enum! {
+    lvgl_sys::lv_chart_update_mode_t,
+    /// What `Chart::set_next_value()` does when all the points are used.
+    pub enum ChartUpdateMode {
+        /// Shift the old points to the left and add the new one on the right
+        Shift = lvgl_sys::LV_CHART_UPDATE_MODE_SHIFT,
+        /// Overwrite the oldest point, starting again from the left
+        Circular = lvgl_sys::LV_CHART_UPDATE_MODE_CIRCULAR,
+    }
+}
+
+/// The ticks drawn along an axis. See `Chart::set_axis_tick()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AxisTicks {
+    pub major_len: lv_coord_t,
+    pub minor_len: lv_coord_t,
+    pub major_count: lv_coord_t,
+    /// The number of minor ticks between two major ticks
+    pub minor_count: lv_coord_t,
+    /// Draw the values next to the major ticks
+    pub labels: bool,
+    /// The extra space reserved around the chart for the ticks and labels
+    pub draw_size: lv_coord_t,
+}
+
+// Shared by a chart and the handles of its series and cursors, which outlive
+// it when the chart is deleted with its parent.
+#[derive(Default)]
+struct ChartState {
+    // Set when the chart is deleted, along with its series and cursors.
+    deleted: Cell<bool>,
+    // The length of the external arrays of the series: the point count can't
+    // exceed them, lvgl would read past their end.
+    ext_y_lens: RefCell<Vec<(NonNull<lvgl_sys::lv_chart_series_t>, usize)>>,
+}
+
+impl ChartState {
+    fn set_ext_y_len(&self, series: NonNull<lvgl_sys::lv_chart_series_t>, len: Option<usize>) {
+        let mut lens = self.ext_y_lens.borrow_mut();
+        lens.retain(|&(s, _)| s != series);
+        if let Some(len) = len {
+            lens.push((series, len));
+        }
+    }
+}
+
+/// A series of a chart, see `Chart::add_series()`. The series is removed from
+/// the chart when the handle is dropped. The handle can outlive the chart.
+pub struct ChartSeries {
+    state: Rc<ChartState>,
+    chart: NonNull<lv_obj_t>,
+    raw: NonNull<lvgl_sys::lv_chart_series_t>,
+    // The external array is read by lvgl until the series is removed.
+    ext_y: Option<Box<[lv_coord_t]>>,
+}
+
+impl Drop for ChartSeries {
+    fn drop(&mut self) {
+        if !self.state.deleted.get() {
+            self.state.set_ext_y_len(self.raw, None);
+            unsafe { lvgl_sys::lv_chart_remove_series(self.chart.as_ptr(), self.raw.as_ptr()) };
+        }
+    }
+}
+
+/// A cursor of a chart, see `Chart::add_cursor()`. Cursors are freed with the
+/// chart.
+pub struct ChartCursor {
+    state: Rc<ChartState>,
+    chart: NonNull<lv_obj_t>,
+    raw: NonNull<lvgl_sys::lv_chart_cursor_t>,
+}
+
+impl<C: 'static> Chart<C> {
+    pub fn set_type(&mut self, chart_type: ChartType) -> &mut Self {
+        unsafe { lvgl_sys::lv_chart_set_type(&mut *self.raw, chart_type.into()) };
+        self
+    }
+
+    pub fn set_update_mode(&mut self, mode: ChartUpdateMode) -> &mut Self {
+        unsafe { lvgl_sys::lv_chart_set_update_mode(&mut *self.raw, mode.into()) };
+        self
+    }
+
+    /// Set the minimum and maximum values of an axis. The default is 0..100.
+    pub fn set_range(&mut self, axis: ChartAxis, min: lv_coord_t, max: lv_coord_t) -> &mut Self {
+        unsafe { lvgl_sys::lv_chart_set_range(&mut *self.raw, axis.into(), min, max) };
+        self
+    }
+
+    pub fn set_axis_tick(&mut self, axis: ChartAxis, ticks: AxisTicks) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_chart_set_axis_tick(
+                &mut *self.raw,
+                axis.into(),
+                ticks.major_len,
+                ticks.minor_len,
+                ticks.major_count,
+                ticks.minor_count,
+                ticks.labels,
+                ticks.draw_size,
+            )
+        };
+        self
+    }
+
+    /// Set the number of points of the series. Panics if it exceeds the
+    /// length of an external array, see `set_ext_y_array()`.
+    pub fn set_point_count(&mut self, count: u16) -> &mut Self {
+        let state = self.state();
+        let too_short = state
+            .ext_y_lens
+            .borrow()
+            .iter()
+            .any(|&(_, len)| len < count.into());
+        assert!(
+            !too_short,
+            "an external array is shorter than the point count"
+        );
+        unsafe { lvgl_sys::lv_chart_set_point_count(&mut *self.raw, count) };
+        self
+    }
+
+    pub fn get_point_count(&self) -> u16 {
+        unsafe { lvgl_sys::lv_chart_get_point_count(&*self.raw) }
+    }
+
+    /// Add a series to the chart, scaled along the range of `axis`.
+    pub fn add_series(&mut self, color: PixelColor, axis: ChartAxis) -> ChartSeries {
+        let raw =
+            unsafe { lvgl_sys::lv_chart_add_series(&mut *self.raw, lv_color(color), axis.into()) };
+        ChartSeries {
+            state: self.state(),
+            chart: NonNull::from(&mut *self.raw),
+            raw: NonNull::new(raw).expect("OOM"),
+            ext_y: None,
+        }
+    }
+
+    pub fn hide_series(&mut self, series: &mut ChartSeries, hide: bool) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_hide_series(&mut *self.raw, series, hide) };
+        self
+    }
+
+    pub fn set_series_color(&mut self, series: &mut ChartSeries, color: PixelColor) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_series_color(&mut *self.raw, series, lv_color(color)) };
+        self
+    }
+
+    /// Add a point to the series, according to the update mode.
+    pub fn set_next_value(&mut self, series: &mut ChartSeries, value: lv_coord_t) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_next_value(&mut *self.raw, series, value) };
+        self
+    }
+
+    /// Set all the points of the series to the same value.
+    pub fn set_all_value(&mut self, series: &mut ChartSeries, value: lv_coord_t) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_all_value(&mut *self.raw, series, value) };
+        self
+    }
+
+    /// Make the series read its Y values from `values`. The array is owned by
+    /// the series, and can be modified with `get_y_array()`.
+    /// Panics if the array is shorter than the point count of the chart.
+    pub fn set_ext_y_array(
+        &mut self,
+        series: &mut ChartSeries,
+        values: impl Into<Box<[lv_coord_t]>>,
+    ) -> &mut Self {
+        let mut values = values.into();
+        assert!(values.len() >= self.get_point_count().into());
+        let raw = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_ext_y_array(&mut *self.raw, raw, values.as_mut_ptr()) };
+        series.state.set_ext_y_len(series.raw, Some(values.len()));
+        // Replacing the previous array is fine, lvgl doesn't use it anymore.
+        series.ext_y = Some(values);
+        self
+    }
+
+    /// The Y values of the series, as many as the point count. Call
+    /// `refresh()` after modifying them. The chart is borrowed as well, as
+    /// `set_point_count()` reallocates the values.
+    pub fn get_y_array<'a>(&'a mut self, series: &'a mut ChartSeries) -> &'a mut [lv_coord_t] {
+        let raw = self.series_ptr(series);
+        let len = self.get_point_count().into();
+        if let Some(ext_y) = series.ext_y.as_mut() {
+            // set_point_count() keeps the array at least as long.
+            return &mut ext_y[..len];
+        }
+        unsafe {
+            let values = lvgl_sys::lv_chart_get_y_array(&*self.raw, raw);
+            core::slice::from_raw_parts_mut(values, len)
+        }
+    }
+
+    /// Add a cursor, drawn as lines in the `dir` directions.
+    pub fn add_cursor(&mut self, color: PixelColor, dir: Dir) -> ChartCursor {
+        let raw =
+            unsafe { lvgl_sys::lv_chart_add_cursor(&mut *self.raw, lv_color(color), dir.bits()) };
+        ChartCursor {
+            state: self.state(),
+            chart: NonNull::from(&mut *self.raw),
+            raw: NonNull::new(raw).expect("OOM"),
+        }
+    }
+
+    /// Move the cursor to a position, in pixels, relative to the chart.
+    pub fn set_cursor_pos(&mut self, cursor: &mut ChartCursor, pos: Point) -> &mut Self {
+        let mut pos = lvgl_sys::lv_point_t {
+            x: pos.x as lv_coord_t,
+            y: pos.y as lv_coord_t,
+        };
+        let cursor = self.cursor_ptr(cursor);
+        unsafe { lvgl_sys::lv_chart_set_cursor_pos(&mut *self.raw, cursor, &mut pos) };
+        self
+    }
+
+    /// Stick the cursor to a point of a series.
+    pub fn set_cursor_point(
+        &mut self,
+        cursor: &mut ChartCursor,
+        series: &ChartSeries,
+        point: u16,
+    ) -> &mut Self {
+        let cursor = self.cursor_ptr(cursor);
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_cursor_point(&mut *self.raw, cursor, series, point) };
+        self
+    }
+
+    pub fn get_cursor_point(&mut self, cursor: &ChartCursor) -> Point {
+        let cursor = self.cursor_ptr(cursor);
+        let point = unsafe { lvgl_sys::lv_chart_get_cursor_point(&mut *self.raw, cursor) };
+        Point::new(point.x.into(), point.y.into())
+    }
+
+    /// The index of the point being pressed, if any.
+    pub fn get_pressed_point(&self) -> Option<u32> {
+        match unsafe { lvgl_sys::lv_chart_get_pressed_point(&*self.raw) } {
+            point if point == lvgl_sys::LV_CHART_POINT_NONE => None,
+            point => Some(point),
+        }
+    }
+
+    // The state shared with the handles, created along with the first one.
+    fn state(&mut self) -> Rc<ChartState> {
+        let slot = event_slot(&mut *self.raw, || StateSlot(Rc::default()));
+        slot.0.clone()
+    }
+
+    // Panics if the series was added to another chart, or if its chart was
+    // deleted and another one took its address.
+    fn series_ptr(&self, series: &ChartSeries) -> *mut lvgl_sys::lv_chart_series_t {
+        assert!(
+            !series.state.deleted.get() && ptr::eq(series.chart.as_ptr(), &*self.raw),
+            "the series belongs to another chart"
+        );
+        series.raw.as_ptr()
+    }
+
+    fn cursor_ptr(&self, cursor: &ChartCursor) -> *mut lvgl_sys::lv_chart_cursor_t {
+        assert!(
+            !cursor.state.deleted.get() && ptr::eq(cursor.chart.as_ptr(), &*self.raw),
+            "the cursor belongs to another chart"
+        );
+        cursor.raw.as_ptr()
+    }
+}
+
+// The state kept by the chart, marked deleted along with it.
+struct StateSlot(Rc<ChartState>);
+
+impl Drop for StateSlot {
+    fn drop(&mut self) {
+        self.0.deleted.set(true);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::ptr;
+    use embedded_graphics::prelude::*;
+
+    use crate::core::PixelColor;
+    use crate::style::Dir;
+    use crate::support::test::with_screen;
+    use crate::widgets::{Chart, ChartAxis};
+
+    #[test]
+    fn series_points() {
+        with_screen(|screen| {
+            let mut chart = Chart::new(screen);
+            chart.set_point_count(4);
+            let mut series = chart.add_series(PixelColor::RED, ChartAxis::PrimaryY);
+            chart.set_all_value(&mut series, 0);
+            chart.set_next_value(&mut series, 7);
+            assert_eq!(chart.get_y_array(&mut series), [7, 0, 0, 0]);
+
+            chart.set_ext_y_array(&mut series, vec![1, 2, 3, 4, 5]);
+            assert_eq!(chart.get_y_array(&mut series), [1, 2, 3, 4]);
+            chart.set_point_count(5);
+            assert_eq!(chart.get_y_array(&mut series), [1, 2, 3, 4, 5]);
+
+            let other = chart.add_series(PixelColor::BLUE, ChartAxis::SecondaryY);
+            drop(series);
+            drop(other);
+            let first = unsafe { lvgl_sys::lv_chart_get_series_next(&*chart.raw, ptr::null()) };
+            assert!(first.is_null());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "shorter than the point count")]
+    fn point_count_bounded_by_ext_array() {
+        with_screen(|screen| {
+            let mut chart = Chart::new(screen);
+            chart.set_point_count(2);
+            let mut series = chart.add_series(PixelColor::RED, ChartAxis::PrimaryY);
+            chart.set_ext_y_array(&mut series, vec![1, 2]);
+            chart.set_point_count(3);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "another chart")]
+    fn series_of_another_chart() {
+        with_screen(|screen| {
+            let mut chart = Chart::new(screen);
+            let mut other = Chart::new(screen);
+            let mut series = other.add_series(PixelColor::RED, ChartAxis::PrimaryY);
+            chart.set_next_value(&mut series, 1);
+        });
+    }
+
+    #[test]
+    fn series_outlives_chart() {
+        with_screen(|screen| {
+            let mut chart = Chart::new(screen);
+            let series = chart.add_series(PixelColor::RED, ChartAxis::PrimaryY);
+            unsafe { lvgl_sys::lv_obj_del(&mut *chart.raw) };
+            // Doesn't remove the series from the deleted chart.
+            drop(series);
+        });
+    }
+
+    #[test]
+    fn cursor_on_point() {
+        with_screen(|screen| {
+            let mut chart = Chart::new(screen);
+            let mut series = chart.add_series(PixelColor::RED, ChartAxis::PrimaryY);
+            let mut cursor = chart.add_cursor(PixelColor::BLUE, Dir::ALL);
+            chart.set_cursor_pos(&mut cursor, Point::new(3, 4));
+            assert_eq!(chart.get_cursor_point(&cursor), Point::new(3, 4));
+
+            chart.set_all_value(&mut series, 50);
+            chart.set_cursor_point(&mut cursor, &series, 0);
+            assert_eq!(chart.get_pressed_point(), None);
+        });
+    }
+}
@@ -0,0 +1,14 @@
+use crate::core::{Obj, ObjExt};
+
+define_object!(Container);
+
+/// A plain object holding other objects, like the pages of a tab view.
+impl<C: 'static> Container<C> {
+    pub fn new(parent: &mut impl ObjExt<C>) -> Self {
+        unsafe {
+            let obj = lvgl_sys::lv_obj_create(&mut *parent.raw);
+            let obj = Obj::from_raw(obj.as_mut().expect("OOM"));
+            Self { obj }
+        }
+    }
+}
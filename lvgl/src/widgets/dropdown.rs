@@ -0,0 +1,57 @@
+use crate::support::{read_cstr, with_options};
+use crate::widgets::Dropdown;
+
+impl<C: 'static> Dropdown<C> {
+    /// Replace the options. The options are copied.
+    pub fn set_options<'a>(&mut self, options: impl IntoIterator<Item = &'a str>) -> &mut Self {
+        with_options(options, |options| unsafe {
+            lvgl_sys::lv_dropdown_set_options(&mut *self.raw, options)
+        });
+        self
+    }
+
+    /// The index of the selected option.
+    pub fn selected(&self) -> usize {
+        unsafe { lvgl_sys::lv_dropdown_get_selected(&*self.raw) }.into()
+    }
+
+    /// Copy the selected option to `text`, truncated to its capacity.
+    pub fn selected_str<const N: usize>(&self, text: &mut heapless::String<N>) {
+        read_cstr(text, |buf, len| unsafe {
+            lvgl_sys::lv_dropdown_get_selected_str(&*self.raw, buf, len)
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::support::test::with_screen;
+    use crate::widgets::Dropdown;
+
+    #[test]
+    fn selected_option() {
+        with_screen(|screen| {
+            let mut dropdown = Dropdown::new(screen);
+            dropdown
+                .set_options(["Low", "Medium", "High"])
+                .set_selected(1);
+
+            let mut text = heapless::String::<16>::new();
+            dropdown.selected_str(&mut text);
+            assert_eq!(dropdown.selected(), 1);
+            assert_eq!(text, "Medium");
+        });
+    }
+
+    #[test]
+    fn selected_str_is_truncated() {
+        with_screen(|screen| {
+            let mut dropdown = Dropdown::new(screen);
+            dropdown.set_options(["Automatic"]);
+
+            let mut text = heapless::String::<5>::new();
+            dropdown.selected_str(&mut text);
+            assert_eq!(text, "Auto");
+        });
+    }
+}
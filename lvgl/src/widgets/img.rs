@@ -0,0 +1,412 @@
+use alloc::{boxed::Box, rc::Rc, vec};
+use core::{convert::Infallible, mem};
+use embedded_graphics::image::ImageDrawable;
+use embedded_graphics::prelude::*;
+use lvgl_sys::{lv_color_t, lv_img_dsc_t};
+
+use crate::core::{event_slot, lv_color, PixelColor};
+use crate::support::with_cstr;
+use crate::widgets::Img;
+
+crate::native_enum! {
+    lvgl_sys::lv_img_cf_t,
+    /// How the pixels of an image are stored.
+    pub enum ImageFormat {
+        /// `lv_color_t` pixels, as `PixelColor`
+        TrueColor = lvgl_sys::LV_IMG_CF_TRUE_COLOR,
+        /// `lv_color_t` pixels, each followed by an alpha byte
+        TrueColorAlpha = lvgl_sys::LV_IMG_CF_TRUE_COLOR_ALPHA,
+        /// Like `TrueColor`, with `LV_COLOR_CHROMA_KEY` pixels transparent
+        TrueColorChromaKeyed = lvgl_sys::LV_IMG_CF_TRUE_COLOR_CHROMA_KEYED,
+        /// A palette of 2 ARGB8888 colors, followed by 1 bit indexes
+        Indexed1Bit = lvgl_sys::LV_IMG_CF_INDEXED_1BIT,
+        Indexed2Bit = lvgl_sys::LV_IMG_CF_INDEXED_2BIT,
+        Indexed4Bit = lvgl_sys::LV_IMG_CF_INDEXED_4BIT,
+        /// A palette of 256 ARGB8888 colors, followed by 8 bit indexes
+        Indexed8Bit = lvgl_sys::LV_IMG_CF_INDEXED_8BIT,
+        /// Opacities only, the image is drawn with the `img_recolor` style
+        Alpha1Bit = lvgl_sys::LV_IMG_CF_ALPHA_1BIT,
+        Alpha2Bit = lvgl_sys::LV_IMG_CF_ALPHA_2BIT,
+        Alpha4Bit = lvgl_sys::LV_IMG_CF_ALPHA_4BIT,
+        Alpha8Bit = lvgl_sys::LV_IMG_CF_ALPHA_8BIT,
+    }
+}
+
+const COLOR_SIZE: usize = mem::size_of::<lv_color_t>();
+
+impl ImageFormat {
+    /// The size in bytes of the pixels of an image, palette included.
+    pub const fn data_size(self, width: u32, height: u32) -> usize {
+        let (w, h) = (width as usize, height as usize);
+        match self {
+            Self::TrueColor | Self::TrueColorChromaKeyed => w * h * COLOR_SIZE,
+            Self::TrueColorAlpha => w * h * (COLOR_SIZE + 1),
+            Self::Indexed1Bit => 4 * 2 + (w + 7) / 8 * h,
+            Self::Indexed2Bit => 4 * 4 + (w + 3) / 4 * h,
+            Self::Indexed4Bit => 4 * 16 + (w + 1) / 2 * h,
+            Self::Indexed8Bit => 4 * 256 + w * h,
+            Self::Alpha1Bit => (w + 7) / 8 * h,
+            Self::Alpha2Bit => (w + 3) / 4 * h,
+            Self::Alpha4Bit => (w + 1) / 2 * h,
+            Self::Alpha8Bit => w * h,
+        }
+    }
+
+    // lvgl reads the pixels of the true color formats as lv_color_t, and the
+    // palettes as lv_color32_t.
+    fn alignment(self) -> usize {
+        match self {
+            Self::TrueColor | Self::TrueColorAlpha | Self::TrueColorChromaKeyed => {
+                mem::align_of::<lv_color_t>()
+            }
+            Self::Indexed1Bit | Self::Indexed2Bit | Self::Indexed4Bit | Self::Indexed8Bit => {
+                mem::align_of::<lvgl_sys::lv_color32_t>()
+            }
+            _ => 1,
+        }
+    }
+
+    /// Convert RGBA8888 pixels, for `include_image!`. `N` must be the
+    /// `data_size()` of the image. Panics with the formats using a palette.
+    #[doc(hidden)]
+    pub const fn convert_rgba<const N: usize>(self, rgba: &[u8], width: u32) -> [u8; N] {
+        let width = width as usize;
+        let mut data = [0; N];
+        let mut i = 0;
+        while i < rgba.len() / 4 {
+            let pixel = [
+                rgba[4 * i],
+                rgba[4 * i + 1],
+                rgba[4 * i + 2],
+                rgba[4 * i + 3],
+            ];
+            match self {
+                Self::TrueColor | Self::TrueColorAlpha => {
+                    let size = self.data_size(1, 1);
+                    let color = color_bytes(pixel[0], pixel[1], pixel[2]);
+                    let mut j = 0;
+                    while j < COLOR_SIZE {
+                        data[i * size + j] = color[j];
+                        j += 1;
+                    }
+                    if size > COLOR_SIZE {
+                        data[i * size + COLOR_SIZE] = pixel[3];
+                    }
+                }
+                Self::Alpha1Bit | Self::Alpha2Bit | Self::Alpha4Bit | Self::Alpha8Bit => {
+                    // The first pixels of a byte are in its highest bits, and
+                    // the rows start on a new byte.
+                    let bits = match self {
+                        Self::Alpha1Bit => 1,
+                        Self::Alpha2Bit => 2,
+                        Self::Alpha4Bit => 4,
+                        _ => 8,
+                    };
+                    let per_byte = 8 / bits;
+                    let (x, y) = (i % width, i / width);
+                    let index = y * ((width + per_byte - 1) / per_byte) + x / per_byte;
+                    let shift = 8 - bits * (x % per_byte + 1);
+                    data[index] |= (pixel[3] >> (8 - bits)) << shift;
+                }
+                _ => panic!("This format can't be converted from RGBA"),
+            }
+            i += 1;
+        }
+        data
+    }
+}
+
+// The bytes of an lv_color_t, in the first COLOR_SIZE bytes.
+const fn color_bytes(r: u8, g: u8, b: u8) -> [u8; 4] {
+    match lvgl_sys::LV_COLOR_DEPTH {
+        32 => [b, g, r, 0xff],
+        16 => {
+            let color = ((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3);
+            let color = if lvgl_sys::LV_COLOR_16_SWAP != 0 {
+                color.swap_bytes()
+            } else {
+                color
+            };
+            let bytes = color.to_ne_bytes();
+            [bytes[0], bytes[1], 0, 0]
+        }
+        8 => [(r >> 5) << 5 | (g >> 5) << 2 | b >> 6, 0, 0, 0],
+        _ => [((r as u16 + g as u16 + b as u16) / 3 > 127) as u8, 0, 0, 0],
+    }
+}
+
+/// Pixels shown by images, see `Img::set_src()`. Cloning a descriptor doesn't
+/// copy the pixels, and the images showing a descriptor keep it alive.
+#[derive(Clone)]
+pub struct ImageDescriptor {
+    inner: Rc<Descriptor>,
+}
+
+struct Descriptor {
+    raw: lv_img_dsc_t,
+    // The pixels of the descriptors made at runtime.
+    _pixels: Option<Box<[lv_color_t]>>,
+}
+
+impl Drop for Descriptor {
+    fn drop(&mut self) {
+        // lvgl may have cached the decoded image under the address of the
+        // descriptor.
+        unsafe { lvgl_sys::lv_img_cache_invalidate_src(self.as_src()) };
+    }
+}
+
+impl Descriptor {
+    fn as_src(&self) -> *const cty::c_void {
+        &self.raw as *const lv_img_dsc_t as *const cty::c_void
+    }
+}
+
+impl ImageDescriptor {
+    /// Describe pixels already in the lvgl format, typically generated by the
+    /// image converter of lvgl. Panics if `data` is too short for the size and
+    /// the format, or if a dimension is 2048 or more.
+    ///
+    /// lvgl reads the colors of the data in place, so it must be aligned like
+    /// them, e.g. in a `#[repr(C, align(4))]` struct. Panics otherwise.
+    pub fn from_static(data: &'static [u8], width: u32, height: u32, format: ImageFormat) -> Self {
+        assert!(
+            data.as_ptr() as usize % format.alignment() == 0,
+            "the image data isn't aligned for its format"
+        );
+        Self::new(data.as_ptr(), data.len(), width, height, format, None)
+    }
+
+    /// Copy an image, like an `ImageRaw<PixelColor>`, in the `TrueColor`
+    /// format. Panics if a dimension is 2048 or more.
+    pub fn from_image(image: &impl ImageDrawable<Color = PixelColor>) -> Self {
+        let size = image.size();
+        let mut pixels = Pixels {
+            size,
+            buf: vec![lv_color_t::default(); (size.width * size.height) as usize]
+                .into_boxed_slice(),
+        };
+        // Can't fail, drawing to memory.
+        let _ = image.draw(&mut pixels);
+        let pixels = pixels.buf;
+        Self::new(
+            pixels.as_ptr() as *const u8,
+            pixels.len() * mem::size_of::<lv_color_t>(),
+            size.width,
+            size.height,
+            ImageFormat::TrueColor,
+            Some(pixels),
+        )
+    }
+
+    fn new(
+        data: *const u8,
+        len: usize,
+        width: u32,
+        height: u32,
+        format: ImageFormat,
+        pixels: Option<Box<[lv_color_t]>>,
+    ) -> Self {
+        // The header holds the dimensions on 11 bits.
+        assert!(width < 2048 && height < 2048);
+        let cf: lvgl_sys::lv_img_cf_t = format.into();
+        let data_size = unsafe {
+            lvgl_sys::lv_img_buf_get_img_size(
+                width as lvgl_sys::lv_coord_t,
+                height as lvgl_sys::lv_coord_t,
+                cf,
+            )
+        };
+        assert!(len >= data_size as usize);
+
+        let mut raw = lv_img_dsc_t::default();
+        raw.header.set_cf(cf.into());
+        raw.header.set_w(width);
+        raw.header.set_h(height);
+        raw.data_size = data_size;
+        raw.data = data;
+        Self {
+            inner: Rc::new(Descriptor {
+                raw,
+                _pixels: pixels,
+            }),
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        let header = &self.inner.raw.header;
+        Size::new(header.w(), header.h())
+    }
+
+    pub(crate) fn as_src(&self) -> *const cty::c_void {
+        self.inner.as_src()
+    }
+}
+
+// Draws an image in the memory of a descriptor.
+struct Pixels {
+    size: Size,
+    buf: Box<[lv_color_t]>,
+}
+
+impl DrawTarget for Pixels {
+    type Color = PixelColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(Point { x, y }, color) in pixels {
+            if x < 0 || y < 0 {
+                continue;
+            }
+            let (x, y) = (x as u32, y as u32);
+            if x < self.size.width && y < self.size.height {
+                self.buf[(y * self.size.width + x) as usize] = lv_color(color);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OriginDimensions for Pixels {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<C: 'static> Img<C> {
+    /// Show the pixels of a descriptor. The image keeps the descriptor alive
+    /// until it's deleted or its source is replaced.
+    pub fn set_src(&mut self, src: &ImageDescriptor) -> &mut Self {
+        unsafe { lvgl_sys::lv_img_set_src(&mut *self.raw, src.as_src()) };
+        self.keep_src(Some(src.clone()));
+        self
+    }
+
+    /// Show a symbol, like `LV_SYMBOL_OK`, drawn with the font of the image.
+    /// A text can follow the symbol. The text is copied.
+    pub fn set_src_symbol(&mut self, symbol: &str) -> &mut Self {
+        with_cstr(symbol, |symbol| unsafe {
+            lvgl_sys::lv_img_set_src(&mut *self.raw, symbol as *const cty::c_void)
+        });
+        self.keep_src(None);
+        self
+    }
+
+    /// The zoom factor, 256 being the original size.
+    pub fn get_zoom(&self) -> u16 {
+        unsafe { lvgl_sys::lv_img_get_zoom(self.raw_mut_const()) }
+    }
+
+    /// The rotation, in 0.1 degrees.
+    pub fn get_angle(&self) -> u16 {
+        unsafe { lvgl_sys::lv_img_get_angle(self.raw_mut_const()) }
+    }
+
+    /// The center of the rotation and of the zoom.
+    pub fn get_pivot(&self) -> Point {
+        let mut pivot = lvgl_sys::lv_point_t::default();
+        unsafe { lvgl_sys::lv_img_get_pivot(self.raw_mut_const(), &mut pivot) };
+        Point::new(pivot.x.into(), pivot.y.into())
+    }
+
+    // Keeps the descriptor shown by the image, until it's replaced.
+    fn keep_src(&mut self, src: Option<ImageDescriptor>) {
+        *event_slot(&mut *self.raw, || None) = src;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::image::ImageRaw;
+    use embedded_graphics::prelude::*;
+    use std::rc::Rc;
+
+    use crate::core::{lv_color, PixelColor};
+    use crate::support::test::with_screen;
+    use crate::widgets::{ImageDescriptor, ImageFormat, Img};
+
+    fn pixel(desc: &ImageDescriptor, x: usize) -> lvgl_sys::lv_color_t {
+        unsafe { *(desc.inner.raw.data as *const lvgl_sys::lv_color_t).add(x) }
+    }
+
+    #[test]
+    fn copy_image_raw() {
+        let data = [0xff; 2 * 4 * core::mem::size_of::<PixelColor>()];
+        let image = ImageRaw::<PixelColor>::new(&data, 4);
+        let desc = ImageDescriptor::from_image(&image);
+
+        assert_eq!(desc.size(), Size::new(4, 2));
+        let white = lv_color(PixelColor::WHITE);
+        assert_eq!(unsafe { pixel(&desc, 7).full }, unsafe { white.full });
+    }
+
+    #[test]
+    fn convert_rgba() {
+        const RGBA: &[u8] = &[0xff, 0, 0, 0x80, 0, 0, 0xff, 0xff, 0, 0, 0, 0];
+        const FORMAT: ImageFormat = ImageFormat::TrueColorAlpha;
+        #[repr(C, align(4))]
+        struct Aligned([u8; FORMAT.data_size(3, 1)]);
+        static ALIGNED: Aligned = Aligned(FORMAT.convert_rgba(RGBA, 3));
+        let data = &ALIGNED.0;
+
+        let red = lv_color(PixelColor::RED);
+        let size = core::mem::size_of::<PixelColor>();
+        assert_eq!(data[..size], unsafe { red.full }.to_ne_bytes()[..]);
+        assert_eq!(data[size], 0x80);
+        assert_eq!(data[3 * (size + 1) - 1], 0);
+        ImageDescriptor::from_static(&ALIGNED.0, 3, 1, FORMAT);
+    }
+
+    #[test]
+    fn convert_rgba_to_alpha() {
+        // Two rows of 5 pixels, opaque on the diagonal
+        let mut rgba = [0; 2 * 5 * 4];
+        rgba[3] = 0xff;
+        rgba[(5 + 1) * 4 + 3] = 0xff;
+        let data: [u8; 4] = ImageFormat::Alpha2Bit.convert_rgba(&rgba, 5);
+
+        assert_eq!(data, [0b1100_0000, 0, 0b0011_0000, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn static_data_too_short() {
+        static DATA: [u8; 4] = [0; 4];
+        ImageDescriptor::from_static(&DATA, 4, 4, ImageFormat::Alpha8Bit);
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't aligned")]
+    fn static_data_misaligned() {
+        #[repr(C, align(4))]
+        struct Aligned([u8; 17]);
+        static DATA: Aligned = Aligned([0; 17]);
+        ImageDescriptor::from_static(&DATA.0[1..], 2, 2, ImageFormat::TrueColor);
+    }
+
+    #[test]
+    fn image_keeps_its_source() {
+        static DATA: [u8; 16] = [0xff; 16];
+        let desc = ImageDescriptor::from_static(&DATA, 4, 4, ImageFormat::Alpha8Bit);
+        with_screen(|screen| {
+            let mut img = Img::new(screen);
+            img.set_src(&desc);
+            assert_eq!(Rc::strong_count(&desc.inner), 2);
+            assert_eq!(
+                unsafe { lvgl_sys::lv_img_get_src(&mut *img.raw) },
+                desc.inner.as_src()
+            );
+
+            img.set_src_symbol("\u{f00c} OK");
+            assert_eq!(Rc::strong_count(&desc.inner), 1);
+
+            img.set_src(&desc).set_zoom(512).set_pivot(1, 2);
+            assert_eq!(img.get_zoom(), 512);
+            assert_eq!(img.get_pivot(), Point::new(1, 2));
+        });
+        assert_eq!(Rc::strong_count(&desc.inner), 1);
+    }
+}
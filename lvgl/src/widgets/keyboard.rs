@@ -0,0 +1,118 @@
+use core::{convert::TryInto, ptr, slice};
+
+use crate::widgets::{BtnMatrixMap, Keyboard, RawBtnMatrixMap, Textarea};
+
+crate::native_enum! {
+    lvgl_sys::lv_keyboard_mode_t,
+    pub enum KeyboardMode {
+        TextLower = lvgl_sys::LV_KEYBOARD_MODE_TEXT_LOWER,
+        TextUpper = lvgl_sys::LV_KEYBOARD_MODE_TEXT_UPPER,
+        Special = lvgl_sys::LV_KEYBOARD_MODE_SPECIAL,
+        Number = lvgl_sys::LV_KEYBOARD_MODE_NUMBER,
+        User1 = lvgl_sys::LV_KEYBOARD_MODE_USER_1,
+        User2 = lvgl_sys::LV_KEYBOARD_MODE_USER_2,
+        User3 = lvgl_sys::LV_KEYBOARD_MODE_USER_3,
+        User4 = lvgl_sys::LV_KEYBOARD_MODE_USER_4,
+    }
+}
+
+impl<C: 'static> Keyboard<C> {
+    /// Type the keys in `textarea`.
+    pub fn set_textarea(&mut self, textarea: &Textarea<C>) -> &mut Self {
+        let textarea = &*textarea.raw as *const _ as *mut _;
+        unsafe { lvgl_sys::lv_keyboard_set_textarea(&mut *self.raw, textarea) };
+        self
+    }
+
+    /// Stop typing in the textarea.
+    pub fn clear_textarea(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_keyboard_set_textarea(&mut *self.raw, ptr::null_mut()) };
+        self
+    }
+
+    pub fn set_mode(&mut self, mode: KeyboardMode) -> &mut Self {
+        unsafe { lvgl_sys::lv_keyboard_set_mode(&mut *self.raw, mode.into()) };
+        self
+    }
+
+    pub fn get_mode(&self) -> KeyboardMode {
+        let mode = unsafe { lvgl_sys::lv_keyboard_get_mode(&*self.raw) };
+        mode.try_into().expect("unknown mode")
+    }
+
+    /// Replace the keys of a mode. lvgl shares the maps between all the
+    /// keyboards: the ones showing the mode switch to the new keys, and the
+    /// map is kept until the mode gets another one.
+    pub fn set_map(&mut self, mode: KeyboardMode, map: BtnMatrixMap) -> &mut Self {
+        let mut mode: lvgl_sys::lv_keyboard_mode_t = mode.into();
+        let maps = unsafe { &mut *ptr::addr_of_mut!(MAPS) };
+        let slot = &mut maps[mode as usize];
+        // Dropped once no keyboard points to it anymore.
+        let _previous = slot.replace(map.into_raw());
+        unsafe {
+            // lvgl only updates the keyboard the map is set on, the walk
+            // reaches all of them, this one included.
+            let mut disp = lvgl_sys::lv_disp_get_next(ptr::null_mut());
+            while let Some(d) = disp.as_mut() {
+                let screens = slice::from_raw_parts(d.screens, d.screen_cnt as usize);
+                for &screen in screens.iter().chain(&[d.top_layer, d.sys_layer]) {
+                    lvgl_sys::lv_obj_tree_walk(
+                        screen,
+                        Some(update_map_cb),
+                        &mut mode as *mut _ as *mut cty::c_void,
+                    );
+                }
+                disp = lvgl_sys::lv_disp_get_next(d);
+            }
+        }
+        self
+    }
+}
+
+// The maps set with `set_map()`, by mode.
+static mut MAPS: [Option<RawBtnMatrixMap>; 8] = [NO_MAP; 8];
+const NO_MAP: Option<RawBtnMatrixMap> = None;
+
+// Set the new map of the mode on a keyboard, it's shown if the keyboard is
+// in this mode.
+unsafe extern "C" fn update_map_cb(
+    obj: *mut lvgl_sys::lv_obj_t,
+    user_data: *mut cty::c_void,
+) -> lvgl_sys::lv_obj_tree_walk_res_t {
+    let mode = *(user_data as *mut lvgl_sys::lv_keyboard_mode_t);
+    if lvgl_sys::lv_obj_check_type(obj, &lvgl_sys::lv_keyboard_class) {
+        let maps = &mut *ptr::addr_of_mut!(MAPS);
+        if let Some(map) = maps[mode as usize].as_mut() {
+            lvgl_sys::lv_keyboard_set_map(obj, mode, map.map.as_mut_ptr(), map.ctrl.as_ptr());
+        }
+    }
+    lvgl_sys::lv_obj_tree_walk_res_t_LV_OBJ_TREE_WALK_NEXT
+}
+
+#[cfg(test)]
+mod test {
+    use cstr_core::CStr;
+
+    use super::KeyboardMode;
+    use crate::support::test::with_screen;
+    use crate::widgets::{BtnMatrixMap, Keyboard};
+
+    #[test]
+    fn set_map_updates_all_keyboards() {
+        with_screen(|screen| {
+            let mut first = Keyboard::new(screen);
+            let mut second = Keyboard::new(screen);
+            first.set_mode(KeyboardMode::User1);
+            second.set_mode(KeyboardMode::User1);
+            for text in ["A", "B"] {
+                let mut map = BtnMatrixMap::new();
+                map.add_row(&[text]);
+                first.set_map(KeyboardMode::User1, map);
+            }
+
+            let text =
+                unsafe { CStr::from_ptr(lvgl_sys::lv_btnmatrix_get_btn_text(&*second.raw, 0)) };
+            assert_eq!(text.to_str(), Ok("B"));
+        });
+    }
+}
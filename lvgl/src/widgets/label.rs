@@ -1,23 +1,151 @@
-/*
+use core::{convert::TryInto, fmt};
+
+use crate::support::with_cstr;
 use crate::widgets::Label;
-use crate::{LvResult, NativeObject};
 
-impl Label {
-    pub fn set_label_align(&mut self, align: LabelAlign) -> LvResult<()> {
+crate::native_enum! {
+    lvgl_sys::lv_label_long_mode_t,
+    /// What to do with the text that doesn't fit in the label.
+    pub enum LabelLongMode {
+        /// Wrap the lines that are too long
+        Wrap = lvgl_sys::LV_LABEL_LONG_WRAP,
+        /// Replace the end of the text with dots
+        Dot = lvgl_sys::LV_LABEL_LONG_DOT,
+        /// Scroll the text back and forth
+        Scroll = lvgl_sys::LV_LABEL_LONG_SCROLL,
+        /// Scroll the text in a loop
+        ScrollCircular = lvgl_sys::LV_LABEL_LONG_SCROLL_CIRCULAR,
+        /// Cut the text that doesn't fit
+        Clip = lvgl_sys::LV_LABEL_LONG_CLIP,
+    }
+}
+
+impl<C: 'static> Label<C> {
+    pub fn get_text(&self) -> &str {
         unsafe {
-            lvgl_sys::lv_label_set_align(self.core.raw()?.as_mut(), align as u8);
+            let text = lvgl_sys::lv_label_get_text(&*self.raw);
+            cstr_core::CStr::from_ptr(text).to_str().unwrap_or_default()
+        }
+    }
+
+    pub fn set_long_mode(&mut self, mode: LabelLongMode) -> &mut Self {
+        unsafe { lvgl_sys::lv_label_set_long_mode(&mut *self.raw, mode.into()) };
+        self
+    }
+
+    pub fn get_long_mode(&self) -> LabelLongMode {
+        let mode = unsafe { lvgl_sys::lv_label_get_long_mode(&*self.raw) };
+        mode.try_into().expect("unknown mode")
+    }
+
+    /// The selected characters, as a range of character indices. Requires
+    /// `LV_LABEL_TEXT_SELECTION` in `lv_conf.h`.
+    pub fn get_text_selection(&self) -> Option<core::ops::Range<u32>> {
+        let (start, end) = unsafe {
+            (
+                lvgl_sys::lv_label_get_text_selection_start(&*self.raw),
+                lvgl_sys::lv_label_get_text_selection_end(&*self.raw),
+            )
+        };
+        if start == lvgl_sys::LV_DRAW_LABEL_NO_TXT_SEL || end == lvgl_sys::LV_DRAW_LABEL_NO_TXT_SEL
+        {
+            None
+        } else {
+            Some(start..end)
         }
-        Ok(())
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-#[repr(u8)]
-pub enum LabelAlign {
-    Left = lvgl_sys::LV_LABEL_ALIGN_LEFT as u8,
-    Center = lvgl_sys::LV_LABEL_ALIGN_CENTER as u8,
-    Right = lvgl_sys::LV_LABEL_ALIGN_RIGHT as u8,
-    Auto = lvgl_sys::LV_LABEL_ALIGN_AUTO as u8,
+/// Appends to the text of the label, e.g. `write!(label, "{}°C", temperature)`.
+/// Use `set_text("")` first to replace the text. A text set with
+/// `set_text_static()` is copied first.
+impl<C: 'static> fmt::Write for Label<C> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let (is_static, text) = unsafe {
+            let label = &*(&*self.raw as *const lvgl_sys::lv_obj_t as *const lvgl_sys::lv_label_t);
+            (label.static_txt() != 0, label.text)
+        };
+        // lv_label_ins_text() ignores static texts.
+        if is_static {
+            unsafe { lvgl_sys::lv_label_set_text(&mut *self.raw, text) };
+        }
+        with_cstr(s, |s| unsafe {
+            lvgl_sys::lv_label_ins_text(&mut *self.raw, lvgl_sys::LV_LABEL_POS_LAST, s)
+        });
+        Ok(())
+    }
 }
 
-*/
+#[cfg(test)]
+mod test {
+    use core::fmt::Write;
+    use cstr_core::CStr;
+
+    use crate::support::test::with_screen;
+    use crate::widgets::{Label, LabelLongMode};
+
+    #[test]
+    fn set_text() {
+        with_screen(|screen| {
+            let mut label = Label::new(screen);
+            label.set_text("Hello");
+
+            assert_eq!(label.get_text(), "Hello");
+        });
+    }
+
+    #[test]
+    fn set_text_stops_at_null() {
+        with_screen(|screen| {
+            let mut label = Label::new(screen);
+            label.set_text("Hello\0World");
+
+            assert_eq!(label.get_text(), "Hello");
+        });
+    }
+
+    #[test]
+    fn write_appends() {
+        with_screen(|screen| {
+            let mut label = Label::new(screen);
+            label.set_text("");
+            write!(label, "{}°C", 21).unwrap();
+            write!(label, " {}%", 40).unwrap();
+
+            assert_eq!(label.get_text(), "21°C 40%");
+        });
+    }
+
+    #[test]
+    fn write_copies_static_text() {
+        with_screen(|screen| {
+            let mut label = Label::new(screen);
+            label.set_text_static(CStr::from_bytes_with_nul(b"Hello\0").unwrap());
+            write!(label, " World").unwrap();
+
+            assert_eq!(label.get_text(), "Hello World");
+        });
+    }
+
+    #[test]
+    fn set_long_mode() {
+        with_screen(|screen| {
+            let mut label = Label::new(screen);
+            label.set_long_mode(LabelLongMode::Dot);
+
+            assert!(matches!(label.get_long_mode(), LabelLongMode::Dot));
+        });
+    }
+
+    #[test]
+    fn text_selection() {
+        with_screen(|screen| {
+            let mut label = Label::new(screen);
+            label.set_text("Hello World");
+            assert_eq!(label.get_text_selection(), None);
+
+            label.set_text_sel_start(6).set_text_sel_end(11);
+            assert_eq!(label.get_text_selection(), Some(6..11));
+        });
+    }
+}
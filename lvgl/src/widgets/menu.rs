@@ -0,0 +1,156 @@
+use core::ptr;
+
+use crate::core::{add_event_cb, with_context, Event, Obj, ObjExt};
+use crate::support::with_cstr;
+use crate::widgets::Menu;
+
+crate::native_enum! {
+    lvgl_sys::lv_menu_mode_header_t,
+    /// Where the header of the pages is, and whether it scrolls with them.
+    pub enum MenuHeaderMode {
+        TopFixed = lvgl_sys::LV_MENU_HEADER_TOP_FIXED,
+        TopUnfixed = lvgl_sys::LV_MENU_HEADER_TOP_UNFIXED,
+        BottomFixed = lvgl_sys::LV_MENU_HEADER_BOTTOM_FIXED,
+    }
+}
+
+define_object!(MenuPage);
+define_object!(MenuSection);
+define_object!(MenuCont);
+define_object!(MenuSeparator);
+
+impl<C: 'static> MenuPage<C> {
+    /// Create a page of `menu`, shown with `Menu::set_page()` or when an
+    /// object registered with `Menu::set_load_page_event()` is clicked.
+    /// The title is copied.
+    pub fn new(menu: &mut Menu<C>, title: &str) -> Self {
+        let obj = with_cstr(title, |title| unsafe {
+            lvgl_sys::lv_menu_page_create(&mut *menu.raw, title as *mut _)
+        });
+        Self {
+            obj: Obj::from_raw(unsafe { obj.as_mut() }.expect("OOM")),
+        }
+    }
+}
+
+impl<C: 'static> MenuSection<C> {
+    /// Create a section grouping the items of a page.
+    pub fn new(page: &mut MenuPage<C>) -> Self {
+        let obj = unsafe { lvgl_sys::lv_menu_section_create(&mut *page.raw) };
+        Self {
+            obj: Obj::from_raw(unsafe { obj.as_mut() }.expect("OOM")),
+        }
+    }
+}
+
+impl<C: 'static> MenuCont<C> {
+    /// Create an item of a page or of a section. The content of the item,
+    /// typically a label, is added to it.
+    pub fn new(parent: &mut impl ObjExt<C>) -> Self {
+        let obj = unsafe { lvgl_sys::lv_menu_cont_create(&mut *parent.raw) };
+        Self {
+            obj: Obj::from_raw(unsafe { obj.as_mut() }.expect("OOM")),
+        }
+    }
+}
+
+impl<C: 'static> MenuSeparator<C> {
+    pub fn new(parent: &mut impl ObjExt<C>) -> Self {
+        let obj = unsafe { lvgl_sys::lv_menu_separator_create(&mut *parent.raw) };
+        Self {
+            obj: Obj::from_raw(unsafe { obj.as_mut() }.expect("OOM")),
+        }
+    }
+}
+
+impl<C: 'static> Menu<C> {
+    /// Show a page in the main area. The previous page is added to the
+    /// history, so that the back button shows it again.
+    pub fn set_page(&mut self, page: &mut MenuPage<C>) -> &mut Self {
+        unsafe { lvgl_sys::lv_menu_set_page(&mut *self.raw, &mut *page.raw) };
+        self
+    }
+
+    /// Show a page in a sidebar, next to the main area, or hide the sidebar.
+    pub fn set_sidebar_page(&mut self, page: Option<&mut MenuPage<C>>) -> &mut Self {
+        let page = page.map_or(ptr::null_mut(), |page| &mut *page.raw as *mut _);
+        unsafe { lvgl_sys::lv_menu_set_sidebar_page(&mut *self.raw, page) };
+        self
+    }
+
+    /// Show `page` when `obj` is clicked.
+    pub fn set_load_page_event(
+        &mut self,
+        obj: &mut impl ObjExt<C>,
+        page: &mut MenuPage<C>,
+    ) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_menu_set_load_page_event(&mut *self.raw, &mut *obj.raw, &mut *page.raw)
+        };
+        self
+    }
+
+    pub fn set_mode_header(&mut self, mode: MenuHeaderMode) -> &mut Self {
+        unsafe { lvgl_sys::lv_menu_set_mode_header(&mut *self.raw, mode.into()) };
+        self
+    }
+
+    /// Show a back button on the root page too. See `on_root_back_btn()`.
+    pub fn set_mode_root_back_btn(&mut self, enabled: bool) -> &mut Self {
+        let mode = if enabled {
+            lvgl_sys::LV_MENU_ROOT_BACK_BTN_ENABLED
+        } else {
+            lvgl_sys::LV_MENU_ROOT_BACK_BTN_DISABLED
+        };
+        unsafe {
+            lvgl_sys::lv_menu_set_mode_root_back_btn(
+                &mut *self.raw,
+                mode as lvgl_sys::lv_menu_mode_root_back_btn_t,
+            )
+        };
+        self
+    }
+
+    /// Register a callback for the back button of the root page, typically
+    /// to close the menu. The other back buttons go back in the history.
+    /// The callback only runs when the event fires from `Lvgl::run_tasks()`.
+    pub fn on_root_back_btn(&mut self, mut f: impl FnMut(&mut C) + 'static) -> &mut Self {
+        add_event_cb(
+            self.raw,
+            Some(Event::Clicked),
+            move |_e, current_target, target| {
+                if unsafe { lvgl_sys::lv_menu_back_btn_is_root(current_target, target) } {
+                    with_context(|context| f(context));
+                }
+            },
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::support::test::with_screen;
+    use crate::widgets::{Label, Menu, MenuCont, MenuPage, MenuSection};
+
+    #[test]
+    fn set_pages() {
+        with_screen(|screen| {
+            let mut menu = Menu::new(screen);
+            let mut sub_page = MenuPage::new(&mut menu, "Settings");
+            let mut main_page = MenuPage::new(&mut menu, "");
+            let mut section = MenuSection::new(&mut main_page);
+            let mut item = MenuCont::new(&mut section);
+            Label::new(&mut item).set_text("Settings");
+            menu.set_load_page_event(&mut item, &mut sub_page)
+                .set_page(&mut main_page);
+
+            let page = unsafe { lvgl_sys::lv_menu_get_cur_main_page(&mut *menu.raw) };
+            assert_eq!(page as *const _, &*main_page.raw as *const _);
+
+            menu.set_page(&mut sub_page);
+            let page = unsafe { lvgl_sys::lv_menu_get_cur_main_page(&mut *menu.raw) };
+            assert_eq!(page as *const _, &*sub_page.raw as *const _);
+        });
+    }
+}
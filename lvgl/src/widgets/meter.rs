@@ -0,0 +1,299 @@
+use alloc::rc::Rc;
+use core::{
+    cell::Cell,
+    ptr::{self, NonNull},
+};
+
+use crate::core::{event_slot, lv_color, PixelColor};
+use crate::widgets::Meter;
+
+// The meter of a handle. `deleted` is shared by the handles of a meter and
+// set when it's deleted, along with its scales and indicators.
+#[derive(Clone)]
+struct MeterRef {
+    meter: NonNull<lvgl_sys::lv_obj_t>,
+    deleted: Rc<Cell<bool>>,
+}
+
+/// A scale of a meter, see `Meter::add_scale()`. Scales are freed with the
+/// meter, and the meter methods panic when given the scale of another meter.
+pub struct MeterScale {
+    meter: MeterRef,
+    raw: NonNull<lvgl_sys::lv_meter_scale_t>,
+}
+
+/// An indicator of a meter: a needle, an arc or colored scale lines.
+/// Indicators are freed with the meter.
+pub struct MeterIndicator {
+    meter: MeterRef,
+    raw: NonNull<lvgl_sys::lv_meter_indicator_t>,
+}
+
+impl<C: 'static> Meter<C> {
+    /// Add a scale. Indicators show values along a scale.
+    pub fn add_scale(&mut self) -> MeterScale {
+        let raw = unsafe { lvgl_sys::lv_meter_add_scale(&mut *self.raw) };
+        MeterScale {
+            meter: self.meter_ref(),
+            raw: NonNull::new(raw).expect("OOM"),
+        }
+    }
+
+    /// Set the number of tick lines of the scale, and how they look.
+    pub fn set_scale_ticks(
+        &mut self,
+        scale: &mut MeterScale,
+        count: u16,
+        width: u16,
+        len: u16,
+        color: PixelColor,
+    ) -> &mut Self {
+        let scale = self.scale_ptr(scale);
+        unsafe {
+            lvgl_sys::lv_meter_set_scale_ticks(
+                &mut *self.raw,
+                scale,
+                count,
+                width,
+                len,
+                lv_color(color),
+            )
+        };
+        self
+    }
+
+    /// Make every `nth` tick a major tick, labeled with its value at
+    /// `label_gap` from the tick.
+    pub fn set_scale_major_ticks(
+        &mut self,
+        scale: &mut MeterScale,
+        nth: u16,
+        width: u16,
+        len: u16,
+        color: PixelColor,
+        label_gap: i16,
+    ) -> &mut Self {
+        let scale = self.scale_ptr(scale);
+        unsafe {
+            lvgl_sys::lv_meter_set_scale_major_ticks(
+                &mut *self.raw,
+                scale,
+                nth,
+                width,
+                len,
+                lv_color(color),
+                label_gap,
+            )
+        };
+        self
+    }
+
+    /// Set the values of the scale, spread over `angle_range` degrees,
+    /// starting `rotation` degrees clockwise from 3 o'clock.
+    pub fn set_scale_range(
+        &mut self,
+        scale: &mut MeterScale,
+        min: i32,
+        max: i32,
+        angle_range: u32,
+        rotation: u32,
+    ) -> &mut Self {
+        let scale = self.scale_ptr(scale);
+        unsafe {
+            lvgl_sys::lv_meter_set_scale_range(
+                &mut *self.raw,
+                scale,
+                min,
+                max,
+                angle_range,
+                rotation,
+            )
+        };
+        self
+    }
+
+    /// Add a needle pointing at the value of the indicator. `r_mod` adjusts
+    /// the length of the needle relative to the radius of the scale.
+    pub fn add_needle_line(
+        &mut self,
+        scale: &mut MeterScale,
+        width: u16,
+        color: PixelColor,
+        r_mod: i16,
+    ) -> MeterIndicator {
+        let scale = self.scale_ptr(scale);
+        let raw = unsafe {
+            lvgl_sys::lv_meter_add_needle_line(&mut *self.raw, scale, width, lv_color(color), r_mod)
+        };
+        self.indicator(raw)
+    }
+
+    /// Add an arc going from the start value to the end value of the
+    /// indicator.
+    pub fn add_arc(
+        &mut self,
+        scale: &mut MeterScale,
+        width: u16,
+        color: PixelColor,
+        r_mod: i16,
+    ) -> MeterIndicator {
+        let scale = self.scale_ptr(scale);
+        let raw = unsafe {
+            lvgl_sys::lv_meter_add_arc(&mut *self.raw, scale, width, lv_color(color), r_mod)
+        };
+        self.indicator(raw)
+    }
+
+    /// Color the tick lines between the start value and the end value of the
+    /// indicator, with a gradient from `color_start` to `color_end`. With
+    /// `local`, the gradient spans the indicator instead of the whole scale.
+    pub fn add_scale_lines(
+        &mut self,
+        scale: &mut MeterScale,
+        color_start: PixelColor,
+        color_end: PixelColor,
+        local: bool,
+        width_mod: i16,
+    ) -> MeterIndicator {
+        let scale = self.scale_ptr(scale);
+        let raw = unsafe {
+            lvgl_sys::lv_meter_add_scale_lines(
+                &mut *self.raw,
+                scale,
+                lv_color(color_start),
+                lv_color(color_end),
+                local,
+                width_mod,
+            )
+        };
+        self.indicator(raw)
+    }
+
+    /// Set the value of a needle, or both the start and end values of an arc
+    /// or of scale lines.
+    pub fn set_indicator_value(&mut self, indicator: &mut MeterIndicator, value: i32) -> &mut Self {
+        let indicator = self.indicator_ptr(indicator);
+        unsafe { lvgl_sys::lv_meter_set_indicator_value(&mut *self.raw, indicator, value) };
+        self
+    }
+
+    pub fn set_indicator_start_value(
+        &mut self,
+        indicator: &mut MeterIndicator,
+        value: i32,
+    ) -> &mut Self {
+        let indicator = self.indicator_ptr(indicator);
+        unsafe { lvgl_sys::lv_meter_set_indicator_start_value(&mut *self.raw, indicator, value) };
+        self
+    }
+
+    pub fn set_indicator_end_value(
+        &mut self,
+        indicator: &mut MeterIndicator,
+        value: i32,
+    ) -> &mut Self {
+        let indicator = self.indicator_ptr(indicator);
+        unsafe { lvgl_sys::lv_meter_set_indicator_end_value(&mut *self.raw, indicator, value) };
+        self
+    }
+
+    fn indicator(&mut self, raw: *mut lvgl_sys::lv_meter_indicator_t) -> MeterIndicator {
+        MeterIndicator {
+            meter: self.meter_ref(),
+            raw: NonNull::new(raw).expect("OOM"),
+        }
+    }
+
+    // The flag set when the meter is deleted, created along with the first handle.
+    fn meter_ref(&mut self) -> MeterRef {
+        let slot = event_slot(&mut *self.raw, || DeletedSlot(Rc::default()));
+        let deleted = slot.0.clone();
+        MeterRef {
+            meter: NonNull::from(&mut *self.raw),
+            deleted,
+        }
+    }
+
+    // Panics if the handle was made by another meter, or if its meter was
+    // deleted and another one took its address.
+    fn check_meter(&self, meter: &MeterRef) {
+        assert!(
+            !meter.deleted.get() && ptr::eq(meter.meter.as_ptr(), &*self.raw),
+            "the handle belongs to another meter"
+        );
+    }
+
+    fn scale_ptr(&self, scale: &MeterScale) -> *mut lvgl_sys::lv_meter_scale_t {
+        self.check_meter(&scale.meter);
+        scale.raw.as_ptr()
+    }
+
+    fn indicator_ptr(&self, indicator: &MeterIndicator) -> *mut lvgl_sys::lv_meter_indicator_t {
+        self.check_meter(&indicator.meter);
+        indicator.raw.as_ptr()
+    }
+}
+
+// The flag kept by the meter, set when it's deleted.
+struct DeletedSlot(Rc<Cell<bool>>);
+
+impl Drop for DeletedSlot {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::prelude::*;
+
+    use crate::core::PixelColor;
+    use crate::support::test::with_screen;
+    use crate::widgets::Meter;
+
+    #[test]
+    fn indicator_values() {
+        with_screen(|screen| {
+            let mut meter = Meter::new(screen);
+            let mut scale = meter.add_scale();
+            meter
+                .set_scale_ticks(&mut scale, 41, 2, 10, PixelColor::BLACK)
+                .set_scale_major_ticks(&mut scale, 8, 4, 15, PixelColor::BLACK, 10)
+                .set_scale_range(&mut scale, 0, 100, 270, 135);
+            let mut needle = meter.add_needle_line(&mut scale, 4, PixelColor::RED, -10);
+            let mut arc = meter.add_arc(&mut scale, 3, PixelColor::BLUE, 0);
+
+            meter.set_indicator_value(&mut needle, 42);
+            meter
+                .set_indicator_start_value(&mut arc, 10)
+                .set_indicator_end_value(&mut arc, 30);
+            let (needle, arc) = unsafe { (needle.raw.as_ref(), arc.raw.as_ref()) };
+            assert_eq!((needle.start_value, needle.end_value), (42, 42));
+            assert_eq!((arc.start_value, arc.end_value), (10, 30));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "another meter")]
+    fn indicator_of_another_meter() {
+        with_screen(|screen| {
+            let mut meter = Meter::new(screen);
+            let mut other = Meter::new(screen);
+            let mut scale = other.add_scale();
+            let mut needle = other.add_needle_line(&mut scale, 4, PixelColor::RED, 0);
+            meter.set_indicator_value(&mut needle, 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "another meter")]
+    fn scale_of_a_deleted_meter() {
+        with_screen(|screen| {
+            let mut meter = Meter::new(screen);
+            let mut scale = meter.add_scale();
+            unsafe { lvgl_sys::lv_obj_del(&mut *meter.raw) };
+            let mut meter = Meter::new(screen);
+            meter.add_scale_lines(&mut scale, PixelColor::RED, PixelColor::BLUE, false, 0);
+        });
+    }
+}
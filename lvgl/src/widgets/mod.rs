@@ -1,7 +1,21 @@
 mod arc;
 mod bar;
-mod gauge;
+mod btnmatrix;
+mod canvas;
+mod chart;
+mod container;
+mod dropdown;
+mod img;
+mod keyboard;
 mod label;
+mod menu;
+mod meter;
+mod msgbox;
+mod roller;
+mod table;
+mod tabview;
+mod textarea;
+mod tileview;
 
 use crate::core::Obj;
 
@@ -9,5 +23,19 @@ include!(concat!(env!("OUT_DIR"), "/generated-widgets.rs"));
 
 pub use arc::*;
 pub use bar::*;
-pub use gauge::*;
+pub use btnmatrix::*;
+pub use canvas::*;
+pub use chart::*;
+pub use container::*;
+pub use dropdown::*;
+pub use img::*;
+pub use keyboard::*;
 pub use label::*;
+pub use menu::*;
+pub use meter::*;
+pub use msgbox::*;
+pub use roller::*;
+pub use table::*;
+pub use tabview::*;
+pub use textarea::*;
+pub use tileview::*;
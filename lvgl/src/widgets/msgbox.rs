@@ -0,0 +1,158 @@
+use core::ptr;
+
+use crate::core::{add_event_cb, keep_alive, with_context, Event, Obj, ObjExt};
+use crate::support::with_cstr;
+use crate::widgets::BtnMatrixMap;
+
+define_object!(MsgBox);
+
+/// A message box: a title, a text and a row of buttons.
+/// ```ignore
+/// let mut dialog = MsgBox::modal("Delete", "Delete the file?", &["Yes", "No"], false);
+/// dialog.on_button(|ui: &mut Ui, btn| {
+///     if btn == 0 {
+///         ui.delete_file();
+///     }
+///     ui.dialog.take().map(MsgBox::close_async);
+/// });
+/// ui.dialog = Some(dialog);
+/// ```
+impl<C: 'static> MsgBox<C> {
+    /// Create a message box on `parent`, with a close button in the title bar
+    /// if `close_btn` is set. The texts are copied.
+    pub fn new(
+        parent: &mut impl ObjExt<C>,
+        title: &str,
+        text: &str,
+        buttons: &[&str],
+        close_btn: bool,
+    ) -> Self {
+        Self::create(&mut *parent.raw, title, text, buttons, close_btn)
+    }
+
+    /// Create a modal message box: it's shown on top of the active screen,
+    /// over a background that blocks the clicks on the screen.
+    pub fn modal(title: &str, text: &str, buttons: &[&str], close_btn: bool) -> Self {
+        Self::create(ptr::null_mut(), title, text, buttons, close_btn)
+    }
+
+    fn create(
+        parent: *mut lvgl_sys::lv_obj_t,
+        title: &str,
+        text: &str,
+        buttons: &[&str],
+        close_btn: bool,
+    ) -> Self {
+        let mut map = (!buttons.is_empty()).then(|| {
+            let mut map = BtnMatrixMap::new();
+            map.add_row(buttons);
+            map.into_raw()
+        });
+        let btn_texts = map
+            .as_mut()
+            .map_or(ptr::null_mut(), |map| map.map.as_mut_ptr());
+        let obj = with_cstr(title, |title| {
+            with_cstr(text, |text| unsafe {
+                lvgl_sys::lv_msgbox_create(parent, title, text, btn_texts, close_btn)
+            })
+        });
+        let obj = Obj::from_raw(unsafe { obj.as_mut() }.expect("OOM"));
+        // The buttons keep pointing to the texts.
+        if let Some(map) = map {
+            keep_alive(obj.raw, map);
+        }
+        Self { obj }
+    }
+
+    /// Register a callback receiving the index of the clicked button.
+    /// The callback only runs when the event fires from `Lvgl::run_tasks()`.
+    pub fn on_button(&mut self, mut f: impl FnMut(&mut C, u16) + 'static) -> &mut Self {
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, _target| {
+                let btn = unsafe { lvgl_sys::lv_msgbox_get_active_btn(current_target) };
+                if u32::from(btn) != lvgl_sys::LV_BTNMATRIX_BTN_NONE {
+                    with_context(|context| f(context, btn));
+                }
+            },
+        );
+        self
+    }
+
+    /// The last clicked button, if any.
+    pub fn get_active_btn(&self) -> Option<u16> {
+        match unsafe { lvgl_sys::lv_msgbox_get_active_btn(self.raw_mut_const()) } {
+            btn if u32::from(btn) == lvgl_sys::LV_BTNMATRIX_BTN_NONE => None,
+            btn => Some(btn),
+        }
+    }
+
+    /// The text of the last clicked button, if any.
+    pub fn active_btn_text(&self) -> Option<&str> {
+        self.get_active_btn()?;
+        unsafe {
+            let text = lvgl_sys::lv_msgbox_get_active_btn_text(self.raw_mut_const());
+            text.as_ref()
+                .map(|text| cstr_core::CStr::from_ptr(text).to_str().unwrap_or_default())
+        }
+    }
+
+    /// Delete the message box, and its background if it's modal. Use
+    /// `close_async()` from the callbacks of the message box instead, it
+    /// must not be deleted while its events are sent.
+    pub fn close(self) {
+        unsafe { lvgl_sys::lv_msgbox_close(&mut *self.obj.raw) };
+    }
+
+    /// Delete the message box on the next call of `Lvgl::run_tasks()`, e.g.
+    /// from its `on_button()` callback.
+    pub fn close_async(self) {
+        unsafe { lvgl_sys::lv_msgbox_close_async(&mut *self.obj.raw) };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cstr_core::CStr;
+
+    use crate::support::test::with_screen;
+    use crate::widgets::MsgBox;
+
+    #[test]
+    fn buttons_are_copied() {
+        with_screen(|screen| {
+            let msgbox = MsgBox::new(screen, "Title", "Text", &["Yes", "No"], false);
+
+            let text = unsafe {
+                let btns = lvgl_sys::lv_msgbox_get_btns(&mut *msgbox.obj.raw);
+                CStr::from_ptr(lvgl_sys::lv_btnmatrix_get_btn_text(btns, 1))
+            };
+            assert_eq!(text.to_str(), Ok("No"));
+            assert_eq!(msgbox.get_active_btn(), None);
+        });
+    }
+
+    #[test]
+    fn close_deletes_the_box() {
+        with_screen(|screen| {
+            let msgbox = MsgBox::new(screen, "Title", "Text", &[], true);
+            assert_eq!(unsafe { lvgl_sys::lv_obj_get_child_cnt(&*screen.raw) }, 1);
+
+            msgbox.close();
+            assert_eq!(unsafe { lvgl_sys::lv_obj_get_child_cnt(&*screen.raw) }, 0);
+        });
+    }
+
+    #[test]
+    fn close_async_deletes_later() {
+        with_screen(|screen| {
+            let msgbox = MsgBox::new(screen, "Title", "Text", &["OK"], false);
+
+            msgbox.close_async();
+            assert_eq!(unsafe { lvgl_sys::lv_obj_get_child_cnt(&*screen.raw) }, 1);
+            unsafe { lvgl_sys::lv_timer_handler() };
+            assert_eq!(unsafe { lvgl_sys::lv_obj_get_child_cnt(&*screen.raw) }, 0);
+        });
+    }
+}
@@ -0,0 +1,59 @@
+use crate::support::{read_cstr, with_options};
+use crate::widgets::Roller;
+
+crate::native_enum! {
+    lvgl_sys::lv_roller_mode_t,
+    pub enum RollerMode {
+        Normal = lvgl_sys::LV_ROLLER_MODE_NORMAL,
+        /// Go back to the first option after the last one
+        Infinite = lvgl_sys::LV_ROLLER_MODE_INFINITE,
+    }
+}
+
+impl<C: 'static> Roller<C> {
+    /// Replace the options. The options are copied.
+    pub fn set_options<'a>(
+        &mut self,
+        options: impl IntoIterator<Item = &'a str>,
+        mode: RollerMode,
+    ) -> &mut Self {
+        with_options(options, |options| unsafe {
+            lvgl_sys::lv_roller_set_options(&mut *self.raw, options, mode.into())
+        });
+        self
+    }
+
+    /// The index of the selected option.
+    pub fn selected(&self) -> usize {
+        unsafe { lvgl_sys::lv_roller_get_selected(&*self.raw) }.into()
+    }
+
+    /// Copy the selected option to `text`, truncated to its capacity.
+    pub fn selected_str<const N: usize>(&self, text: &mut heapless::String<N>) {
+        read_cstr(text, |buf, len| unsafe {
+            lvgl_sys::lv_roller_get_selected_str(&*self.raw, buf, len)
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::style::Animation;
+    use crate::support::test::with_screen;
+    use crate::widgets::{Roller, RollerMode};
+
+    #[test]
+    fn selected_option() {
+        with_screen(|screen| {
+            let mut roller = Roller::new(screen);
+            roller
+                .set_options(["Mon", "Tue", "Wed"], RollerMode::Infinite)
+                .set_selected(2, Animation::Off);
+
+            let mut text = heapless::String::<16>::new();
+            roller.selected_str(&mut text);
+            assert_eq!(roller.selected(), 2);
+            assert_eq!(text, "Wed");
+        });
+    }
+}
@@ -0,0 +1,166 @@
+use core::convert::TryFrom;
+use lvgl_sys::lv_coord_t;
+
+use crate::support::with_cstr;
+use crate::widgets::Table;
+
+bitflags! {
+    pub struct TableCellCtrl: lvgl_sys::lv_table_cell_ctrl_t {
+        /// Merge the cell with the one on its right
+        const MERGE_RIGHT = lvgl_sys::LV_TABLE_CELL_CTRL_MERGE_RIGHT;
+        /// Don't wrap the text of the cell
+        const TEXT_CROP = lvgl_sys::LV_TABLE_CELL_CTRL_TEXT_CROP;
+        const CUSTOM_1 = lvgl_sys::LV_TABLE_CELL_CTRL_CUSTOM_1;
+        const CUSTOM_2 = lvgl_sys::LV_TABLE_CELL_CTRL_CUSTOM_2;
+        const CUSTOM_3 = lvgl_sys::LV_TABLE_CELL_CTRL_CUSTOM_3;
+        const CUSTOM_4 = lvgl_sys::LV_TABLE_CELL_CTRL_CUSTOM_4;
+    }
+}
+
+impl<C: 'static> Table<C> {
+    /// Set the text of a cell. The text is copied, and the table grows if
+    /// needed.
+    pub fn set_cell(&mut self, row: u16, col: u16, text: &str) -> &mut Self {
+        with_cstr(text, |text| unsafe {
+            lvgl_sys::lv_table_set_cell_value(&mut *self.raw, row, col, text)
+        });
+        self
+    }
+
+    /// The text of a cell, empty if the cell isn't set.
+    pub fn get_cell(&self, row: u16, col: u16) -> &str {
+        unsafe {
+            let text = lvgl_sys::lv_table_get_cell_value(self.raw_mut_const(), row, col);
+            cstr_core::CStr::from_ptr(text).to_str().unwrap_or_default()
+        }
+    }
+
+    /// Replace the content of the table, one array of cells per row.
+    /// Panics past 65535 rows or columns, the maximum of lvgl.
+    pub fn set_rows<'a, const N: usize>(
+        &mut self,
+        rows: impl IntoIterator<Item = [&'a str; N]>,
+    ) -> &mut Self {
+        let col_count = cell_count(N, "columns");
+        unsafe { lvgl_sys::lv_table_set_col_cnt(&mut *self.raw, col_count) };
+        let mut row_count = 0;
+        for (row, texts) in rows.into_iter().enumerate() {
+            // Checks the count, so that the row index fits as well.
+            row_count = cell_count(row + 1, "rows");
+            for (col, text) in (0..col_count).zip(texts.iter()) {
+                self.set_cell(row_count - 1, col, text);
+            }
+        }
+        unsafe { lvgl_sys::lv_table_set_row_cnt(&mut *self.raw, row_count) };
+        self
+    }
+
+    /// Set the number of columns and their widths.
+    /// Panics past 65535 columns, the maximum of lvgl.
+    pub fn set_col_widths(&mut self, widths: &[lv_coord_t]) -> &mut Self {
+        let col_count = cell_count(widths.len(), "columns");
+        unsafe {
+            lvgl_sys::lv_table_set_col_cnt(&mut *self.raw, col_count);
+            for (col, width) in (0..col_count).zip(widths) {
+                lvgl_sys::lv_table_set_col_width(&mut *self.raw, col, *width);
+            }
+        }
+        self
+    }
+
+    pub fn get_col_width(&self, col: u16) -> lv_coord_t {
+        unsafe { lvgl_sys::lv_table_get_col_width(self.raw_mut_const(), col) }
+    }
+
+    pub fn add_cell_ctrl(&mut self, row: u16, col: u16, ctrl: TableCellCtrl) -> &mut Self {
+        unsafe { lvgl_sys::lv_table_add_cell_ctrl(&mut *self.raw, row, col, ctrl.bits()) };
+        self
+    }
+
+    pub fn clear_cell_ctrl(&mut self, row: u16, col: u16, ctrl: TableCellCtrl) -> &mut Self {
+        unsafe { lvgl_sys::lv_table_clear_cell_ctrl(&mut *self.raw, row, col, ctrl.bits()) };
+        self
+    }
+
+    /// Whether the cell has all the flags of `ctrl`.
+    pub fn has_cell_ctrl(&self, row: u16, col: u16, ctrl: TableCellCtrl) -> bool {
+        unsafe { lvgl_sys::lv_table_has_cell_ctrl(self.raw_mut_const(), row, col, ctrl.bits()) }
+    }
+
+    /// The `(row, col)` of the cell being pressed or selected with a keypad,
+    /// if any.
+    pub fn selected_cell(&self) -> Option<(u16, u16)> {
+        let (mut row, mut col) = (0, 0);
+        unsafe { lvgl_sys::lv_table_get_selected_cell(self.raw_mut_const(), &mut row, &mut col) };
+        if row == lvgl_sys::LV_TABLE_CELL_NONE as u16 || col == lvgl_sys::LV_TABLE_CELL_NONE as u16
+        {
+            None
+        } else {
+            Some((row, col))
+        }
+    }
+}
+
+// lvgl counts the rows and columns with u16.
+fn cell_count(count: usize, what: &str) -> u16 {
+    u16::try_from(count).unwrap_or_else(|_| panic!("too many {} for a table", what))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::support::test::with_screen;
+    use crate::widgets::{Table, TableCellCtrl};
+
+    #[test]
+    fn rows_and_cells() {
+        with_screen(|screen| {
+            let mut table = Table::new(screen);
+            table.set_rows([["a", "b"], ["c", "d"], ["e", "f"]]);
+            let (rows, cols) = unsafe {
+                (
+                    lvgl_sys::lv_table_get_row_cnt(&mut *table.raw),
+                    lvgl_sys::lv_table_get_col_cnt(&mut *table.raw),
+                )
+            };
+            assert_eq!((rows, cols), (3, 2));
+            assert_eq!(table.get_cell(2, 1), "f");
+
+            table.set_cell(0, 0, "changed");
+            assert_eq!(table.get_cell(0, 0), "changed");
+            assert_eq!(table.selected_cell(), None);
+        });
+    }
+
+    #[test]
+    fn col_widths() {
+        with_screen(|screen| {
+            let mut table = Table::new(screen);
+            table.set_col_widths(&[40, 60, 80]);
+            let cols = unsafe { lvgl_sys::lv_table_get_col_cnt(&mut *table.raw) };
+            assert_eq!(cols, 3);
+            assert_eq!(table.get_col_width(2), 80);
+        });
+    }
+
+    #[test]
+    fn cell_ctrl() {
+        with_screen(|screen| {
+            let mut table = Table::new(screen);
+            table.set_rows([["a", "b"]]);
+            table.add_cell_ctrl(0, 0, TableCellCtrl::MERGE_RIGHT | TableCellCtrl::TEXT_CROP);
+            table.clear_cell_ctrl(0, 0, TableCellCtrl::TEXT_CROP);
+            assert!(table.has_cell_ctrl(0, 0, TableCellCtrl::MERGE_RIGHT));
+            assert!(!table.has_cell_ctrl(0, 0, TableCellCtrl::TEXT_CROP));
+            assert!(!table.has_cell_ctrl(0, 1, TableCellCtrl::MERGE_RIGHT));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "too many columns")]
+    fn too_many_columns() {
+        with_screen(|screen| {
+            let widths = vec![10; 70_000];
+            Table::new(screen).set_col_widths(&widths);
+        });
+    }
+}
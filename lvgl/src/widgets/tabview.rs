@@ -0,0 +1,63 @@
+use core::ptr;
+
+use crate::core::{add_event_cb, with_context, Event, Obj};
+use crate::support::with_cstr;
+use crate::widgets::{Container, Tabview};
+
+impl<C: 'static> Tabview<C> {
+    /// Add a tab, with a button named `name`. The name is copied. The
+    /// returned page holds the content of the tab.
+    pub fn add_tab(&mut self, name: &str) -> Container<C> {
+        let obj = with_cstr(name, |name| unsafe {
+            lvgl_sys::lv_tabview_add_tab(&mut *self.raw, name)
+        });
+        Container {
+            obj: Obj::from_raw(unsafe { obj.as_mut() }.expect("OOM")),
+        }
+    }
+
+    /// The index of the active tab.
+    pub fn get_tab_act(&self) -> u16 {
+        unsafe { lvgl_sys::lv_tabview_get_tab_act(self.raw_mut_const()) }
+    }
+
+    /// Register a callback receiving the index of the new active tab.
+    /// The callback only runs when the event fires from `Lvgl::run_tasks()`.
+    pub fn on_tab_changed(&mut self, mut f: impl FnMut(&mut C, u16) + 'static) -> &mut Self {
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, target| {
+                // Ignore the values changed in the tabs.
+                if ptr::eq(current_target, target) {
+                    let tab = unsafe { lvgl_sys::lv_tabview_get_tab_act(current_target) };
+                    with_context(|context| f(context, tab));
+                }
+            },
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::style::{Animation, Dir};
+    use crate::support::test::with_screen;
+    use crate::widgets::{Label, Tabview};
+
+    #[test]
+    fn switch_tabs() {
+        with_screen(|screen| {
+            let mut tabview = Tabview::new(screen, Dir::TOP, 40);
+            let mut first = tabview.add_tab("First");
+            let second = tabview.add_tab("Second");
+            Label::new(&mut first).set_text("Content");
+
+            assert_eq!(tabview.get_tab_act(), 0);
+            tabview.set_act(1, Animation::Off);
+            assert_eq!(tabview.get_tab_act(), 1);
+            assert_eq!(unsafe { lvgl_sys::lv_obj_get_child_cnt(&*first.raw) }, 1);
+            assert_eq!(unsafe { lvgl_sys::lv_obj_get_child_cnt(&*second.raw) }, 0);
+        });
+    }
+}
@@ -0,0 +1,73 @@
+use crate::widgets::Textarea;
+
+impl<C: 'static> Textarea<C> {
+    pub fn get_text(&self) -> &str {
+        unsafe {
+            let text = lvgl_sys::lv_textarea_get_text(&*self.raw);
+            cstr_core::CStr::from_ptr(text).to_str().unwrap_or_default()
+        }
+    }
+
+    /// The position of the cursor, in characters.
+    pub fn get_cursor_pos(&self) -> u32 {
+        unsafe { lvgl_sys::lv_textarea_get_cursor_pos(&*self.raw) }
+    }
+
+    pub fn get_password_mode(&self) -> bool {
+        unsafe { lvgl_sys::lv_textarea_get_password_mode(&*self.raw) }
+    }
+
+    pub fn get_one_line(&self) -> bool {
+        unsafe { lvgl_sys::lv_textarea_get_one_line(&*self.raw) }
+    }
+
+    /// The maximum number of characters, zero if there is no limit.
+    pub fn get_max_length(&self) -> u32 {
+        unsafe { lvgl_sys::lv_textarea_get_max_length(self.raw_mut_const()) }
+    }
+
+    /// The characters that can be typed, all of them if `None`.
+    pub fn get_accepted_chars(&self) -> Option<&str> {
+        unsafe {
+            let chars = lvgl_sys::lv_textarea_get_accepted_chars(self.raw_mut_const());
+            chars.as_ref().map(|chars| {
+                cstr_core::CStr::from_ptr(chars)
+                    .to_str()
+                    .unwrap_or_default()
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::support::test::with_screen;
+    use crate::widgets::Textarea;
+
+    #[test]
+    fn add_text() {
+        with_screen(|screen| {
+            let mut textarea = Textarea::new(screen);
+            textarea.set_text("Hello").add_text(" World");
+
+            assert_eq!(textarea.get_text(), "Hello World");
+            assert_eq!(textarea.get_cursor_pos(), 11);
+        });
+    }
+
+    #[test]
+    fn max_length_and_accepted_chars() {
+        with_screen(|screen| {
+            let mut textarea = Textarea::new(screen);
+            textarea
+                .set_max_length(4)
+                .set_accepted_chars(cstr_core::CStr::from_bytes_with_nul(b"0123456789\0").unwrap())
+                .set_text("")
+                .add_text("12a345");
+
+            assert_eq!(textarea.get_text(), "1234");
+            assert_eq!(textarea.get_max_length(), 4);
+            assert_eq!(textarea.get_accepted_chars(), Some("0123456789"));
+        });
+    }
+}
@@ -0,0 +1,53 @@
+use crate::core::Obj;
+use crate::style::{Animation, Dir};
+use crate::widgets::Tileview;
+
+define_object!(Tile);
+
+impl<C: 'static> Tileview<C> {
+    /// Add a tile at column `col` and row `row`. The user can swipe from the
+    /// tile to its neighbours in the `dir` directions. The returned tile holds
+    /// the content of the tile.
+    pub fn add_tile(&mut self, col: u8, row: u8, dir: Dir) -> Tile<C> {
+        let obj = unsafe { lvgl_sys::lv_tileview_add_tile(&mut *self.raw, col, row, dir.bits()) };
+        Tile {
+            obj: Obj::from_raw(unsafe { obj.as_mut() }.expect("OOM")),
+        }
+    }
+
+    /// Scroll to a tile.
+    pub fn set_tile(&mut self, tile: &mut Tile<C>, anim: Animation) -> &mut Self {
+        unsafe { lvgl_sys::lv_obj_set_tile(&mut *self.raw, &mut *tile.raw, anim.into()) };
+        self
+    }
+
+    /// Scroll to the tile at column `col` and row `row`.
+    pub fn set_tile_id(&mut self, col: u32, row: u32, anim: Animation) -> &mut Self {
+        unsafe { lvgl_sys::lv_obj_set_tile_id(&mut *self.raw, col, row, anim.into()) };
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::style::{Animation, Dir};
+    use crate::support::test::with_screen;
+    use crate::widgets::Tileview;
+
+    #[test]
+    fn switch_tiles() {
+        with_screen(|screen| {
+            let mut tileview = Tileview::new(screen);
+            let mut first = tileview.add_tile(0, 0, Dir::RIGHT);
+            let second = tileview.add_tile(1, 0, Dir::LEFT);
+
+            tileview.set_tile_id(1, 0, Animation::Off);
+            let active = unsafe { lvgl_sys::lv_tileview_get_tile_act(&mut *tileview.raw) };
+            assert_eq!(active as *const _, &*second.raw as *const _);
+
+            tileview.set_tile(&mut first, Animation::Off);
+            let active = unsafe { lvgl_sys::lv_tileview_get_tile_act(&mut *tileview.raw) };
+            assert_eq!(active as *const _, &*first.raw as *const _);
+        });
+    }
+}